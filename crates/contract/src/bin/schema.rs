@@ -1,10 +1,12 @@
 use cosmwasm_schema::write_api;
-use cw_eth2_lc::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw_eth2_lc::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
+        sudo: SudoMsg,
     }
 }
@@ -0,0 +1,95 @@
+use super::Contract;
+use crate::error::ContractError;
+use cosmwasm_std::{Addr, Coin, Deps, DepsMut};
+
+impl Contract<'_> {
+    /// Returns the message sender, failing for contexts without one (e.g. queries)
+    pub fn sender(&self) -> Result<&Addr, ContractError> {
+        self.ctx
+            .info
+            .as_ref()
+            .map(|info| &info.sender)
+            .ok_or(ContractError::Unauthorized {})
+    }
+
+    /// Funds sent along with the message
+    pub fn funds(&self) -> &[Coin] {
+        self.ctx
+            .info
+            .as_ref()
+            .map(|info| info.funds.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn assert_admin(&self, deps: Deps) -> Result<(), ContractError> {
+        if self.ctx.sudo {
+            return Ok(());
+        }
+
+        let non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        match non_mapped_state.admin {
+            Some(admin) if &admin == self.sender()? => Ok(()),
+            _ => Err(ContractError::Unauthorized {}),
+        }
+    }
+
+    /// First step of an admin transfer, the new admin has to accept it
+    pub fn propose_admin(&self, deps: DepsMut, new_admin: String) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let new_admin = deps.api.addr_validate(&new_admin)?;
+        self.state.pending_admin.save(deps.storage, &new_admin)?;
+
+        Ok(())
+    }
+
+    pub fn accept_admin(&self, deps: DepsMut) -> Result<(), ContractError> {
+        let pending_admin = self
+            .state
+            .pending_admin
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoPendingAdmin {})?;
+        if &pending_admin != self.sender()? {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let mut non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        non_mapped_state.admin = Some(pending_admin);
+        self.state
+            .non_mapped
+            .save(deps.storage, &non_mapped_state)?;
+        self.state.pending_admin.remove(deps.storage);
+
+        Ok(())
+    }
+
+    /// Drops the admin for good, admin gated messages can no longer be executed
+    pub fn renounce_admin(&self, deps: DepsMut) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let mut non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        non_mapped_state.admin = None;
+        self.state
+            .non_mapped
+            .save(deps.storage, &non_mapped_state)?;
+        self.state.pending_admin.remove(deps.storage);
+
+        Ok(())
+    }
+
+    /// Circuit breaker for light client updates, queries keep being served
+    pub fn set_paused(&self, deps: DepsMut, paused: bool) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+        self.state.paused.save(deps.storage, &paused)?;
+
+        Ok(())
+    }
+
+    pub fn is_paused(&self, deps: Deps) -> Result<bool, ContractError> {
+        Ok(self
+            .state
+            .paused
+            .may_load(deps.storage)?
+            .unwrap_or_default())
+    }
+}
@@ -1,104 +1,250 @@
-use super::Contract;
+use super::{new_vkey_version, Contract};
+use crate::error::ContractError;
 use crate::eth_utility::compute_sync_committee_period;
-use crate::msg::LightClientUpdate;
-use cosmwasm_std::DepsMut;
+use crate::events::LightClientEvent;
+use crate::msg::{Circuit, FinalizedHeaderInfo, LightClientUpdate};
+use cosmwasm_std::{DepsMut, Order};
+
+/// State changes made by an accepted light client update
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateOutcome {
+    pub head_advanced: bool,
+    pub sync_committee_added: bool,
+}
+
+impl UpdateOutcome {
+    /// Whether the update added anything, resubmissions of known updates don't
+    pub fn progressed(&self) -> bool {
+        self.head_advanced || self.sync_committee_added
+    }
+}
 
 impl Contract<'_> {
-    pub fn update_light_client(&self, deps: DepsMut, light_client_update: LightClientUpdate) {
-        let mut non_mapped_state = self.state.non_mapped.load(deps.storage).unwrap();
+    pub fn update_light_client(
+        &self,
+        mut deps: DepsMut,
+        light_client_update: LightClientUpdate,
+    ) -> Result<UpdateOutcome, ContractError> {
+        if self.is_paused(deps.as_ref())? {
+            return Err(ContractError::Paused {});
+        }
+        self.assert_relayer(deps.as_ref())?;
+
+        let mut non_mapped_state = self.state.non_mapped.load(deps.storage)?;
 
         // processing lc_update
         if light_client_update.finalized_slot < non_mapped_state.head_slot {
-            panic!("Finalized slot is behind head slot");
+            return Err(ContractError::FinalizedSlotBehindHead {
+                finalized_slot: light_client_update.finalized_slot,
+                head_slot: non_mapped_state.head_slot,
+            });
         }
 
-        if light_client_update.participation * 3 < 2 * 512 {
-            panic!("Participation is less than 2/3rd threshold");
-        }
+        self.assert_participation(deps.as_ref(), &light_client_update)?;
 
-        self.lc_update_proof_verify(deps.as_ref(), light_client_update.clone());
+        self.lc_update_proof_verify(deps.as_ref(), light_client_update.clone())?;
+        let mut outcome = UpdateOutcome {
+            head_advanced: light_client_update.finalized_slot > non_mapped_state.head_slot,
+            ..Default::default()
+        };
         non_mapped_state.head_slot = light_client_update.finalized_slot;
         self.state
             .non_mapped
-            .save(deps.storage, &non_mapped_state)
-            .unwrap();
+            .save(deps.storage, &non_mapped_state)?;
 
         // updating roots
+        self.resolve_pending_updates(deps.branch(), &light_client_update)?;
+        self.save_finalized_roots(
+            deps.branch(),
+            light_client_update.finalized_slot,
+            light_client_update.finalized_header_root.clone(),
+            light_client_update.execution_state_root.clone(),
+            self.header_provenance(
+                Some(light_client_update.participation),
+                Some(light_client_update.attested_slot),
+            ),
+        )?;
+        if outcome.head_advanced {
+            self.head_updated(
+                deps.as_ref(),
+                light_client_update.finalized_slot,
+                light_client_update.finalized_header_root.clone(),
+                light_client_update.execution_state_root.clone(),
+            )?;
+        }
+
+        // processing sc_update
+        if let Some(next_sync_committee_update) = light_client_update.clone().next_sync_committee {
+            self.sc_update_proof_verify(deps.as_ref(), light_client_update.clone())?;
+            let next_period = compute_sync_committee_period(light_client_update.finalized_slot) + 1;
+            let hash = self
+                .state
+                .mapped
+                .sync_committee_poseidon_hashes
+                .may_load(deps.storage, next_period)?;
+            match hash {
+                Some(h) => {
+                    if next_sync_committee_update.sync_committee_poseidon_hash != h {
+                        return Err(ContractError::ConflictingSyncCommitteeHash {
+                            period: next_period,
+                        });
+                    }
+                }
+                None => {
+                    self.state.mapped.sync_committee_poseidon_hashes.save(
+                        deps.storage,
+                        next_period,
+                        &next_sync_committee_update.sync_committee_poseidon_hash,
+                    )?;
+                    outcome.sync_committee_added = true;
+                    self.emit(LightClientEvent::SyncCommitteeAdded {
+                        period: next_period,
+                        sync_committee_poseidon_hash: next_sync_committee_update
+                            .sync_committee_poseidon_hash,
+                    });
+                }
+            }
+        }
+
+        self.prune_roots(deps.branch(), non_mapped_state.head_slot)?;
+
+        if outcome.progressed() {
+            self.pay_reward(deps)?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Stores the roots of a finalized slot, fails if different roots are already stored.
+    /// `info` replaces the stored provenance until a proven update attests the root.
+    pub(crate) fn save_finalized_roots(
+        &self,
+        deps: DepsMut,
+        slot: u64,
+        header_root: Vec<u8>,
+        execution_state_root: Vec<u8>,
+        info: FinalizedHeaderInfo,
+    ) -> Result<(), ContractError> {
         let root = self
             .state
             .mapped
             .header_roots
-            .may_load(deps.storage, light_client_update.finalized_slot)
-            .unwrap();
+            .may_load(deps.storage, slot)?;
         match root {
-            Some(r) => assert_eq!(
-                &light_client_update.finalized_header_root, &r,
-                "Header root already set with different value"
-            ),
+            Some(r) => {
+                if header_root != r {
+                    return Err(ContractError::ConflictingHeaderRoot { slot });
+                }
+            }
             None => {
                 self.state
                     .mapped
                     .header_roots
-                    .save(
-                        deps.storage,
-                        light_client_update.finalized_slot,
-                        &light_client_update.finalized_header_root,
-                    )
-                    .unwrap();
+                    .save(deps.storage, slot, &header_root)?;
+                self.emit(LightClientEvent::RootRecorded {
+                    slot,
+                    header_root,
+                    execution_state_root: execution_state_root.clone(),
+                });
             }
         }
 
+        let proven = self
+            .state
+            .mapped
+            .finalized_header_infos
+            .may_load(deps.storage, slot)?
+            .is_some_and(|stored| stored.participation.is_some());
+        if !proven {
+            self.state
+                .mapped
+                .finalized_header_infos
+                .save(deps.storage, slot, &info)?;
+        }
+
         let root = self
             .state
             .mapped
             .execution_state_roots
-            .may_load(deps.storage, light_client_update.finalized_slot)
-            .unwrap();
+            .may_load(deps.storage, slot)?;
         match root {
-            Some(r) => assert_eq!(
-                &light_client_update.execution_state_root, &r,
-                "Header root already set with different value"
-            ),
+            Some(r) => {
+                if execution_state_root != r {
+                    return Err(ContractError::ConflictingExecutionStateRoot { slot });
+                }
+            }
             None => {
-                self.state
-                    .mapped
-                    .execution_state_roots
-                    .save(
-                        deps.storage,
-                        light_client_update.finalized_slot,
-                        &light_client_update.execution_state_root,
-                    )
-                    .unwrap();
+                self.state.mapped.execution_state_roots.save(
+                    deps.storage,
+                    slot,
+                    &execution_state_root,
+                )?;
             }
         }
 
-        // processing sc_update
-        if let Some(next_sync_committee_update) = light_client_update.clone().next_sync_committee {
-            self.sc_update_proof_verify(deps.as_ref(), light_client_update.clone());
-            let next_period = compute_sync_committee_period(light_client_update.finalized_slot) + 1;
-            let hash = self
-                .state
-                .mapped
-                .sync_committee_poseidon_hashes
-                .may_load(deps.storage, next_period)
-                .unwrap();
-            match hash {
-                Some(h) => assert_eq!(
-                    &next_sync_committee_update.sync_committee_poseidon_hash, &h,
-                    "Sync Committee poseidon hash already set with different value"
-                ),
-                None => {
-                    self.state
-                        .mapped
-                        .sync_committee_poseidon_hashes
-                        .save(
-                            deps.storage,
-                            next_period,
-                            &next_sync_committee_update.sync_committee_poseidon_hash,
-                        )
-                        .unwrap();
-                }
-            }
+        Ok(())
+    }
+
+    /// Applies `updates` in order, e.g. a sync committee update before the
+    /// next period's lc updates
+    pub fn update_light_client_batch(
+        &self,
+        mut deps: DepsMut,
+        updates: Vec<LightClientUpdate>,
+    ) -> Result<Vec<UpdateOutcome>, ContractError> {
+        if updates.is_empty() {
+            return Err(ContractError::MalformedUpdate {
+                reason: "batch contains no updates".into(),
+            });
         }
+
+        updates
+            .into_iter()
+            .enumerate()
+            .map(|(index, update)| {
+                self.update_light_client(deps.branch(), update)
+                    .map_err(|e| ContractError::BatchUpdate {
+                        index: index as u64,
+                        source: Box::new(e),
+                    })
+            })
+            .collect()
+    }
+
+    pub fn register_verifying_key(
+        &self,
+        deps: DepsMut,
+        circuit: Circuit,
+        vkey_string: String,
+        activation_slot: u64,
+    ) -> Result<u64, ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let vkeys = self.state.mapped.vkeys(&circuit);
+        let (latest_activation_slot, latest) = vkeys
+            .range(deps.storage, None, None, Order::Descending)
+            .next()
+            .ok_or(ContractError::NoVerifyingKey {
+                slot: activation_slot,
+            })??;
+
+        // keys can only be added for slots the light client has not reached yet
+        let head_slot = self.state.non_mapped.load(deps.storage)?.head_slot;
+        let min_slot = latest_activation_slot.max(head_slot);
+        if activation_slot <= min_slot {
+            return Err(ContractError::InvalidActivationSlot {
+                activation_slot,
+                min_slot,
+            });
+        }
+
+        let version = latest.version + 1;
+        vkeys.save(
+            deps.storage,
+            activation_slot,
+            &new_vkey_version(version, activation_slot, vkey_string)?,
+        )?;
+
+        Ok(version)
     }
 }
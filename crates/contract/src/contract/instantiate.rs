@@ -1,66 +1,98 @@
-use super::Contract;
+use super::{new_vkey_version, Contract};
+use crate::error::ContractError;
 use crate::eth_utility::{compute_sync_committee_period, Network};
-use crate::msg::InitInput;
-use crate::state::{NonMappedState, NonMappedStateLC, NonMappedStateSC};
-use cosmwasm_std::DepsMut;
-use electron_rs::verifier::near::{get_prepared_verifying_key, parse_verification_key};
+use crate::msg::{Checkpoint, InitInput};
+use crate::state::NonMappedState;
+use cosmwasm_std::{DepsMut, Empty};
 use std::str::FromStr;
 
 // version info for migration info
-const CONTRACT_NAME: &str = "crates.io:cw-eth2-cl";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "crates.io:cw-eth2-cl";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 impl Contract<'_> {
-    pub fn init(&mut self, deps: DepsMut, args: InitInput) {
-        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION).unwrap();
-
-        let vkey_lc_update =
-            get_prepared_verifying_key(parse_verification_key(args.vkey_lc_update_string).unwrap());
-        let vkey_sc_update =
-            get_prepared_verifying_key(parse_verification_key(args.vkey_sc_update_string).unwrap());
+    pub fn init(&mut self, mut deps: DepsMut, args: InitInput) -> Result<(), ContractError> {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
         self.state
             .mapped
             .header_roots
-            .save(deps.storage, args.head_slot, &args.header_root)
-            .unwrap();
+            .save(deps.storage, args.head_slot, &args.header_root)?;
 
-        self.state
-            .mapped
-            .execution_state_roots
-            .save(deps.storage, args.head_slot, &args.execution_state_root)
-            .unwrap();
+        self.state.mapped.execution_state_roots.save(
+            deps.storage,
+            args.head_slot,
+            &args.execution_state_root,
+        )?;
+        self.state.mapped.finalized_header_infos.save(
+            deps.storage,
+            args.head_slot,
+            &self.header_provenance(None, None),
+        )?;
 
         let period = compute_sync_committee_period(args.head_slot);
-        self.state
-            .mapped
-            .sync_committee_poseidon_hashes
-            .save(deps.storage, period, &args.sync_committee_poseidon_hash)
-            .unwrap();
+        self.state.mapped.sync_committee_poseidon_hashes.save(
+            deps.storage,
+            period,
+            &args.sync_committee_poseidon_hash,
+        )?;
 
-        let network =
-            Network::from_str(args.network.as_str()).unwrap_or_else(|e| panic!("{}", e.as_str()));
+        let network = Network::from_str(args.network.as_str()).map_err(|_| {
+            ContractError::UnknownNetwork {
+                network: args.network.clone(),
+            }
+        })?;
 
-        self.state
-            .non_mapped
-            .save(
-                deps.storage,
-                &NonMappedState {
-                    admin: args.admin,
-                    network,
-                    head_slot: args.head_slot,
-                },
-            )
-            .unwrap();
+        self.state.non_mapped.save(
+            deps.storage,
+            &NonMappedState {
+                admin: Some(args.admin),
+                network,
+                head_slot: args.head_slot,
+            },
+        )?;
 
-        self.state
-            .non_mapped_lc
-            .save(deps.storage, &NonMappedStateLC { vkey_lc_update })
-            .unwrap();
+        if let Some(relayers) = args.relayers {
+            self.state.relayer_allowlist.save(deps.storage, &true)?;
+            for relayer in relayers {
+                let relayer = deps.api.addr_validate(relayer.as_str())?;
+                self.state
+                    .mapped
+                    .relayers
+                    .save(deps.storage, &relayer, &Empty {})?;
+            }
+        }
 
-        self.state
-            .non_mapped_sc
-            .save(deps.storage, &NonMappedStateSC { vkey_sc_update })
-            .unwrap();
+        if let Some(participation) = args.participation {
+            self.save_participation_config(deps.branch(), participation)?;
+        }
+
+        if let Some(retention_slots) = args.retention_slots {
+            self.state
+                .retention_slots
+                .save(deps.storage, &retention_slots)?;
+        }
+
+        self.emit_checkpoint(&Checkpoint {
+            head_slot: args.head_slot,
+            header_root: args.header_root,
+            execution_state_root: args.execution_state_root,
+            sync_committee_poseidon_hash: args.sync_committee_poseidon_hash,
+        });
+
+        // initial verifying keys are active from genesis
+        self.state.mapped.lc_update_vkeys.save(
+            deps.storage,
+            0,
+            &new_vkey_version(1, 0, args.vkey_lc_update_string)?,
+        )?;
+
+        self.state.mapped.sc_update_vkeys.save(
+            deps.storage,
+            0,
+            &new_vkey_version(1, 0, args.vkey_sc_update_string)?,
+        )?;
+
+        Ok(())
     }
 }
@@ -0,0 +1,82 @@
+use super::instantiate::{CONTRACT_NAME, CONTRACT_VERSION};
+use super::{prepared_vkey_version, Contract};
+use crate::error::ContractError;
+use crate::state::{v0_1_0, NonMappedState};
+use cosmwasm_std::DepsMut;
+
+type Version = (u64, u64, u64);
+
+impl Contract<'_> {
+    /// Migrates stored state to the layout of this code version, returns the version migrated from
+    pub fn migrate(&self, mut deps: DepsMut) -> Result<String, ContractError> {
+        let stored = cw2::get_contract_version(deps.storage)?;
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::InvalidMigrationContract {
+                expected: CONTRACT_NAME.to_string(),
+                found: stored.contract,
+            });
+        }
+
+        let from = parse_version(&stored.version)?;
+        if from > parse_version(CONTRACT_VERSION)? {
+            return Err(ContractError::MigrationDowngrade {
+                from: stored.version,
+                to: CONTRACT_VERSION.to_string(),
+            });
+        }
+
+        // storage transforms, applied in order of the version that introduced them
+        if from < (0, 2, 0) {
+            self.migrate_from_v0_1_0(deps.branch())?;
+        }
+
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        Ok(stored.version)
+    }
+
+    /// v0.2.0 made the admin optional and moved verifying keys into versioned maps
+    fn migrate_from_v0_1_0(&self, deps: DepsMut) -> Result<(), ContractError> {
+        let non_mapped_state = v0_1_0::NON_MAPPED.load(deps.storage)?;
+        self.state.non_mapped.save(
+            deps.storage,
+            &NonMappedState {
+                admin: Some(non_mapped_state.admin),
+                network: non_mapped_state.network,
+                head_slot: non_mapped_state.head_slot,
+            },
+        )?;
+
+        let vkey_lc_update = v0_1_0::NON_MAPPED_LC.load(deps.storage)?.vkey_lc_update;
+        self.state.mapped.lc_update_vkeys.save(
+            deps.storage,
+            0,
+            &prepared_vkey_version(1, 0, vkey_lc_update)?,
+        )?;
+        v0_1_0::NON_MAPPED_LC.remove(deps.storage);
+
+        let vkey_sc_update = v0_1_0::NON_MAPPED_SC.load(deps.storage)?.vkey_sc_update;
+        self.state.mapped.sc_update_vkeys.save(
+            deps.storage,
+            0,
+            &prepared_vkey_version(1, 0, vkey_sc_update)?,
+        )?;
+        v0_1_0::NON_MAPPED_SC.remove(deps.storage);
+
+        Ok(())
+    }
+}
+
+/// Parses `major.minor.patch`, ignoring pre-release and build metadata
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    let invalid = || ContractError::InvalidContractVersion {
+        version: version.to_string(),
+    };
+
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts = core.split('.').map(|part| part.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok((major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
@@ -1,13 +1,29 @@
+pub mod admin;
 pub mod execute;
 pub mod instantiate;
+pub mod migrate;
+pub mod optimistic;
+pub mod participation;
 pub mod prover;
 pub mod query;
+pub mod recovery;
+pub mod relayer;
+pub mod retention;
+pub mod reward;
+pub mod subscription;
 
+use crate::error::ContractError;
 use crate::eth_utility::compute_sync_committee_period;
-use crate::msg::{LightClientUpdate, NextSyncCommittee};
-use crate::state::ContractState;
-use cosmwasm_std::{Attribute, Deps, Env, MessageInfo, Response};
-use electron_rs::verifier::near::verify_proof;
+use crate::events::LightClientEvent;
+use crate::msg::{Checkpoint, Circuit, FinalizedHeaderInfo, LightClientUpdate, NextSyncCommittee};
+use crate::state::{ContractState, VerifyingKeyVersion};
+use cosmwasm_std::{
+    to_vec, Addr, Attribute, BankMsg, Coin, Deps, Env, Event, MessageInfo, Order, Response, SubMsg,
+};
+use cw_storage_plus::Bound;
+use electron_rs::verifier::near::{
+    get_prepared_verifying_key, parse_verification_key, verify_proof, PreparedVerifyingKey,
+};
 use ethereum_types::U256;
 use sha2::{Digest, Sha256};
 use std::cell::RefCell;
@@ -15,11 +31,17 @@ use std::cell::RefCell;
 pub struct ContractContext {
     pub env: Env,
     pub info: Option<MessageInfo>,
+    /// Called by chain governance, which has admin rights
+    pub sudo: bool,
 }
 
 impl ContractContext {
     pub fn new(env: Env, info: Option<MessageInfo>) -> Self {
-        Self { env, info }
+        Self {
+            env,
+            info,
+            sudo: false,
+        }
     }
 }
 
@@ -27,6 +49,8 @@ pub struct Contract<'a> {
     pub ctx: ContractContext,
     pub state: ContractState<'a>,
     logs: RefCell<Vec<String>>,
+    messages: RefCell<Vec<SubMsg>>,
+    events: RefCell<Vec<Event>>,
 }
 
 impl Contract<'_> {
@@ -35,19 +59,121 @@ impl Contract<'_> {
             ctx: ContractContext::new(env, info),
             state: ContractState::new(),
             logs: RefCell::new(vec![]),
+            messages: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
         }
     }
 
-    // attach logs to instruction response
+    pub fn new_sudo(env: Env) -> Self {
+        let mut contract = Self::new(env, None);
+        contract.ctx.sudo = true;
+        contract
+    }
+
+    // attach logs, events and queued messages to instruction response
     pub fn response_with_logs(&self, mut res: Response) -> Response {
         for log in self.logs.borrow().iter() {
             res.attributes.push(Attribute::new("log", log));
         }
 
-        res
+        res.add_events(self.events.take())
+            .add_submessages(self.messages.take())
+    }
+
+    /// Emits the head update event and notifies subscribers
+    pub(crate) fn head_updated(
+        &self,
+        deps: Deps,
+        slot: u64,
+        header_root: Vec<u8>,
+        execution_state_root: Vec<u8>,
+    ) -> Result<(), ContractError> {
+        self.notify_subscribers(deps, slot, &header_root, &execution_state_root)?;
+        self.emit(LightClientEvent::HeadUpdated {
+            slot,
+            period: compute_sync_committee_period(slot),
+            header_root,
+            execution_state_root,
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn emit(&self, event: LightClientEvent) {
+        self.events.borrow_mut().push(event.into());
+    }
+
+    /// Events for state seeded from a trusted checkpoint, at init or re-bootstrap
+    pub(crate) fn emit_checkpoint(&self, checkpoint: &Checkpoint) {
+        let period = compute_sync_committee_period(checkpoint.head_slot);
+        self.emit(LightClientEvent::RootRecorded {
+            slot: checkpoint.head_slot,
+            header_root: checkpoint.header_root.clone(),
+            execution_state_root: checkpoint.execution_state_root.clone(),
+        });
+        self.emit(LightClientEvent::HeadUpdated {
+            slot: checkpoint.head_slot,
+            period,
+            header_root: checkpoint.header_root.clone(),
+            execution_state_root: checkpoint.execution_state_root.clone(),
+        });
+        self.emit(LightClientEvent::SyncCommitteeAdded {
+            period,
+            sync_committee_poseidon_hash: checkpoint.sync_committee_poseidon_hash.clone(),
+        });
+    }
+
+    /// Provenance of roots stored by the current message
+    pub(crate) fn header_provenance(
+        &self,
+        participation: Option<u64>,
+        attested_slot: Option<u64>,
+    ) -> FinalizedHeaderInfo {
+        FinalizedHeaderInfo {
+            participation,
+            attested_slot,
+            submitter: self.ctx.info.as_ref().map(|info| info.sender.clone()),
+            block_height: self.ctx.env.block.height,
+            block_time: self.ctx.env.block.time.seconds(),
+        }
+    }
+
+    /// Queues a bank transfer from the contract, sent with the response
+    pub(crate) fn send(&self, to: &Addr, amount: Coin) {
+        self.messages.borrow_mut().push(SubMsg::new(BankMsg::Send {
+            to_address: to.to_string(),
+            amount: vec![amount],
+        }));
+    }
+
+    /// Latest verifying key version of `circuit` activated at or before `slot`
+    pub(crate) fn vkey_at_slot(
+        &self,
+        deps: Deps,
+        circuit: &Circuit,
+        slot: u64,
+    ) -> Result<VerifyingKeyVersion, ContractError> {
+        let (_, vkey_version) = self
+            .state
+            .mapped
+            .vkeys(circuit)
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::inclusive(slot)),
+                Order::Descending,
+            )
+            .next()
+            .ok_or(ContractError::NoVerifyingKey { slot })??;
+
+        Ok(vkey_version)
     }
 
-    fn lc_update_proof_verify(&self, deps: Deps, light_client_update: LightClientUpdate) {
+    fn lc_update_proof_verify(
+        &self,
+        deps: Deps,
+        light_client_update: LightClientUpdate,
+    ) -> Result<(), ContractError> {
         let mut attested_slot_le = vec![0u8; 8];
         attested_slot_le.clone_from_slice(&light_client_update.attested_slot.to_le_bytes());
         attested_slot_le.extend_from_slice(&[0u8; 24]);
@@ -62,16 +188,14 @@ impl Contract<'_> {
 
         let finalized_period = compute_sync_committee_period(light_client_update.attested_slot);
 
-        let sync_committee_poseidon = match self
+        let sync_committee_poseidon = self
             .state
             .mapped
             .sync_committee_poseidon_hashes
-            .load(deps.storage, finalized_period)
-            .ok()
-        {
-            Some(hash) => hash,
-            None => panic!("Sync committee hash not known"),
-        };
+            .may_load(deps.storage, finalized_period)?
+            .ok_or(ContractError::UnknownSyncCommittee {
+                period: finalized_period,
+            })?;
 
         let mut hasher = Sha256::new();
         let mut sha256_input = [attested_slot_le, finalized_slot_le].concat();
@@ -104,26 +228,42 @@ impl Contract<'_> {
 
         let public_inputs = format!("{:?}", vec![hash_and_mask.to_string()]);
 
-        let non_mapped_state_lc = self.state.non_mapped_lc.load(deps.storage).unwrap();
-        let vkey_lc_update = non_mapped_state_lc.vkey_lc_update;
+        let vkey_lc_update = self
+            .vkey_at_slot(deps, &Circuit::LcUpdate, light_client_update.attested_slot)?
+            .vkey;
 
-        assert!(
-            verify_proof(
-                vkey_lc_update,
-                light_client_update.lc_update_proof,
-                public_inputs
-            )
-            .unwrap(),
-            "Failed to verify lc_update proof"
-        );
+        let verified = verify_proof(
+            vkey_lc_update,
+            light_client_update.lc_update_proof,
+            public_inputs,
+        )
+        .map_err(|e| ContractError::ProofVerification {
+            reason: format!("{e:?}"),
+        })?;
+        if !verified {
+            return Err(ContractError::InvalidLcUpdateProof {});
+        }
+
+        Ok(())
     }
 
-    fn sc_update_proof_verify(&self, deps: Deps, light_client_update: LightClientUpdate) {
+    fn sc_update_proof_verify(
+        &self,
+        deps: Deps,
+        light_client_update: LightClientUpdate,
+    ) -> Result<(), ContractError> {
         let NextSyncCommittee {
             sync_committee_ssz,
             sync_committee_poseidon_hash,
             sc_update_proof,
-        } = light_client_update.next_sync_committee.unwrap();
+        } = light_client_update
+            .next_sync_committee
+            .ok_or(ContractError::MissingNextSyncCommittee {})?;
+        if sync_committee_ssz.len() < 32 || light_client_update.finalized_header_root.len() < 32 {
+            return Err(ContractError::MalformedUpdate {
+                reason: "sync committee ssz and finalized header root must be 32 bytes".into(),
+            });
+        }
         let mut public_inputs = vec![String::new(); 65];
         for i in 0..32 {
             public_inputs[i] = sync_committee_ssz[i].to_string();
@@ -134,12 +274,56 @@ impl Contract<'_> {
         }
         let public_inputs = format!("{:?}", public_inputs);
 
-        let non_mapped_state_sc = self.state.non_mapped_sc.load(deps.storage).unwrap();
-        let vkey_sc_update = non_mapped_state_sc.vkey_sc_update;
+        let vkey_sc_update = self
+            .vkey_at_slot(deps, &Circuit::ScUpdate, light_client_update.attested_slot)?
+            .vkey;
+
+        let verified =
+            verify_proof(vkey_sc_update, sc_update_proof, public_inputs).map_err(|e| {
+                ContractError::ProofVerification {
+                    reason: format!("{e:?}"),
+                }
+            })?;
+        if !verified {
+            return Err(ContractError::InvalidScUpdateProof {});
+        }
 
-        assert!(
-            verify_proof(vkey_sc_update, sc_update_proof, public_inputs).unwrap(),
-            "Failed to verify sc_update proof"
-        );
+        Ok(())
     }
 }
+
+pub(crate) fn new_vkey_version(
+    version: u64,
+    activation_slot: u64,
+    vkey_string: String,
+) -> Result<VerifyingKeyVersion, ContractError> {
+    prepared_vkey_version(
+        version,
+        activation_slot,
+        prepare_verifying_key(vkey_string)?,
+    )
+}
+
+pub(crate) fn prepared_vkey_version(
+    version: u64,
+    activation_slot: u64,
+    vkey: PreparedVerifyingKey,
+) -> Result<VerifyingKeyVersion, ContractError> {
+    let vkey_hash = Sha256::digest(to_vec(&vkey)?).to_vec();
+
+    Ok(VerifyingKeyVersion {
+        version,
+        activation_slot,
+        vkey_hash,
+        vkey,
+    })
+}
+
+fn prepare_verifying_key(vkey_string: String) -> Result<PreparedVerifyingKey, ContractError> {
+    let vkey =
+        parse_verification_key(vkey_string).map_err(|e| ContractError::InvalidVerifyingKey {
+            reason: format!("{e:?}"),
+        })?;
+
+    Ok(get_prepared_verifying_key(vkey))
+}
@@ -0,0 +1,320 @@
+use super::Contract;
+use crate::error::ContractError;
+use crate::eth_utility::{
+    compute_sync_committee_period, NetworkConfig, EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SLOTS_PER_EPOCH,
+};
+use crate::msg::{FinalizedHeaderInfo, LightClientUpdate, OptimisticConfig, PendingUpdate};
+use cosmwasm_std::{Deps, DepsMut, Order, OverflowError, OverflowOperation, StdResult};
+use cw_storage_plus::Bound;
+
+/// Most slots an optimistic update may finalize behind its attested slot,
+/// finality normally lags by two epochs
+pub const MAX_FINALITY_DELAY: u64 = SLOTS_PER_EPOCH * EPOCHS_PER_SYNC_COMMITTEE_PERIOD;
+/// Longest challenge period in seconds
+pub const MAX_CHALLENGE_PERIOD: u64 = 30 * 24 * 60 * 60;
+/// Max pending updates settled per proven update, any others are settled by
+/// the following updates
+const MAX_SETTLED_PER_UPDATE: usize = 16;
+
+impl Contract<'_> {
+    /// Posts `light_client_update` without verifying its proofs, returns the challenge deadline
+    pub fn submit_optimistic_update(
+        &self,
+        deps: DepsMut,
+        light_client_update: LightClientUpdate,
+    ) -> Result<u64, ContractError> {
+        if self.is_paused(deps.as_ref())? {
+            return Err(ContractError::Paused {});
+        }
+        self.assert_relayer(deps.as_ref())?;
+
+        let config = self
+            .state
+            .optimistic_config
+            .may_load(deps.storage)?
+            .ok_or(ContractError::OptimisticModeDisabled {})?;
+        if self.funds() != [config.bond.clone()] {
+            return Err(ContractError::InvalidBond { bond: config.bond });
+        }
+
+        let head_slot = self.state.non_mapped.load(deps.storage)?.head_slot;
+        let finalized_slot = light_client_update.finalized_slot;
+        if finalized_slot <= head_slot {
+            return Err(ContractError::FinalizedSlotBehindHead {
+                finalized_slot,
+                head_slot,
+            });
+        }
+        self.assert_participation(deps.as_ref(), &light_client_update)?;
+        // sync committees only change through proven updates
+        if light_client_update.next_sync_committee.is_some() {
+            return Err(ContractError::MalformedUpdate {
+                reason: "optimistic updates can't carry a sync committee".into(),
+            });
+        }
+        self.assert_provable(
+            deps.as_ref(),
+            light_client_update.attested_slot,
+            finalized_slot,
+        )?;
+        if self
+            .state
+            .mapped
+            .pending_updates
+            .has(deps.storage, finalized_slot)
+        {
+            return Err(ContractError::PendingUpdateExists {
+                slot: finalized_slot,
+            });
+        }
+
+        let now = self.ctx.env.block.time.seconds();
+        let challenge_deadline = now.checked_add(config.challenge_period).ok_or_else(|| {
+            OverflowError::new(OverflowOperation::Add, now, config.challenge_period)
+        })?;
+        self.state.mapped.pending_updates.save(
+            deps.storage,
+            finalized_slot,
+            &PendingUpdate {
+                relayer: self.sender()?.clone(),
+                bond: config.bond,
+                attested_slot: light_client_update.attested_slot,
+                finalized_slot,
+                finalized_header_root: light_client_update.finalized_header_root,
+                execution_state_root: light_client_update.execution_state_root,
+                challenge_deadline,
+            },
+        )?;
+
+        Ok(challenge_deadline)
+    }
+
+    /// Slashes the pending update for `pending_slot`, the finalized slot of the
+    /// proven `light_client_update` if unset, when the proven update disproves it
+    pub fn challenge_optimistic_update(
+        &self,
+        deps: DepsMut,
+        light_client_update: LightClientUpdate,
+        pending_slot: Option<u64>,
+    ) -> Result<(), ContractError> {
+        let slot = pending_slot.unwrap_or(light_client_update.finalized_slot);
+        let pending_update = self
+            .state
+            .mapped
+            .pending_updates
+            .may_load(deps.storage, slot)?
+            .ok_or(ContractError::NoPendingUpdate { slot })?;
+
+        self.assert_participation(deps.as_ref(), &light_client_update)?;
+        self.lc_update_proof_verify(deps.as_ref(), light_client_update.clone())?;
+        if !disproves(&light_client_update, &pending_update) {
+            return Err(ContractError::ChallengeDoesNotDisprove { slot });
+        }
+
+        self.state.mapped.pending_updates.remove(deps.storage, slot);
+        self.send(self.sender()?, pending_update.bond);
+
+        Ok(())
+    }
+
+    /// Applies the pending update for `finalized_slot` once it can no longer be
+    /// challenged. Slots the head already reached are left for proven updates to settle.
+    pub fn finalize_optimistic_update(
+        &self,
+        mut deps: DepsMut,
+        finalized_slot: u64,
+    ) -> Result<(), ContractError> {
+        if self.is_paused(deps.as_ref())? {
+            return Err(ContractError::Paused {});
+        }
+
+        let pending_update = self
+            .state
+            .mapped
+            .pending_updates
+            .may_load(deps.storage, finalized_slot)?
+            .ok_or(ContractError::NoPendingUpdate {
+                slot: finalized_slot,
+            })?;
+        if self.ctx.env.block.time.seconds() < pending_update.challenge_deadline {
+            return Err(ContractError::ChallengePeriodActive {
+                deadline: pending_update.challenge_deadline,
+            });
+        }
+        let mut non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        if finalized_slot <= non_mapped_state.head_slot {
+            return Err(ContractError::FinalizedSlotBehindHead {
+                finalized_slot,
+                head_slot: non_mapped_state.head_slot,
+            });
+        }
+        // the committee could have been replaced since, e.g. by a rebootstrap
+        self.assert_provable(deps.as_ref(), pending_update.attested_slot, finalized_slot)?;
+
+        self.save_finalized_roots(
+            deps.branch(),
+            finalized_slot,
+            pending_update.finalized_header_root.clone(),
+            pending_update.execution_state_root.clone(),
+            FinalizedHeaderInfo {
+                submitter: Some(pending_update.relayer.clone()),
+                ..self.header_provenance(None, Some(pending_update.attested_slot))
+            },
+        )?;
+        non_mapped_state.head_slot = finalized_slot;
+        self.state
+            .non_mapped
+            .save(deps.storage, &non_mapped_state)?;
+        self.head_updated(
+            deps.as_ref(),
+            finalized_slot,
+            pending_update.finalized_header_root,
+            pending_update.execution_state_root,
+        )?;
+        self.state
+            .mapped
+            .pending_updates
+            .remove(deps.storage, finalized_slot);
+        self.send(&pending_update.relayer, pending_update.bond);
+
+        self.prune_roots(deps, non_mapped_state.head_slot)
+    }
+
+    pub fn set_optimistic_config(
+        &self,
+        deps: DepsMut,
+        config: Option<OptimisticConfig>,
+    ) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        match config {
+            Some(config) => {
+                if config.challenge_period > MAX_CHALLENGE_PERIOD {
+                    return Err(ContractError::InvalidOptimisticConfig {
+                        reason: format!(
+                            "challenge period must not exceed {MAX_CHALLENGE_PERIOD} seconds"
+                        ),
+                    });
+                }
+                self.state.optimistic_config.save(deps.storage, &config)?
+            }
+            None => self.state.optimistic_config.remove(deps.storage),
+        }
+
+        Ok(())
+    }
+
+    /// Settles the pending updates the proven `light_client_update` moves the
+    /// head to or past, they can't be finalized anymore. Bonds of updates it
+    /// disproves, or whose roots conflict with stored ones, go to the sender,
+    /// the others are refunded.
+    pub(crate) fn resolve_pending_updates(
+        &self,
+        deps: DepsMut,
+        light_client_update: &LightClientUpdate,
+    ) -> Result<(), ContractError> {
+        let passed_updates = self
+            .state
+            .mapped
+            .pending_updates
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::inclusive(light_client_update.finalized_slot)),
+                Order::Ascending,
+            )
+            .take(MAX_SETTLED_PER_UPDATE)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (slot, pending_update) in passed_updates {
+            self.state.mapped.pending_updates.remove(deps.storage, slot);
+            if disproves(light_client_update, &pending_update)
+                || self.conflicts_with_stored_roots(deps.as_ref(), &pending_update)?
+            {
+                self.send(self.sender()?, pending_update.bond);
+            } else {
+                self.send(&pending_update.relayer, pending_update.bond);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Contract<'_> {
+    /// An optimistic update can only be disproven, and its head followed, by
+    /// proven updates. It must attest a slot that already started with a known
+    /// sync committee, and finalize a slot at most [`MAX_FINALITY_DELAY`] before it.
+    fn assert_provable(
+        &self,
+        deps: Deps,
+        attested_slot: u64,
+        finalized_slot: u64,
+    ) -> Result<(), ContractError> {
+        if finalized_slot > attested_slot || attested_slot - finalized_slot > MAX_FINALITY_DELAY {
+            return Err(ContractError::MalformedUpdate {
+                reason: format!(
+                    "finalized slot {finalized_slot} must be at most {MAX_FINALITY_DELAY} slots before attested slot {attested_slot}"
+                ),
+            });
+        }
+
+        let network = self.state.non_mapped.load(deps.storage)?.network;
+        let attested_time = NetworkConfig::new(&network).compute_timestamp_at_slot(attested_slot);
+        if attested_time > self.ctx.env.block.time.seconds() {
+            return Err(ContractError::MalformedUpdate {
+                reason: format!("attested slot {attested_slot} has not started yet"),
+            });
+        }
+
+        let period = compute_sync_committee_period(attested_slot);
+        if !self
+            .state
+            .mapped
+            .sync_committee_poseidon_hashes
+            .has(deps.storage, period)
+        {
+            return Err(ContractError::UnknownSyncCommittee { period });
+        }
+
+        Ok(())
+    }
+
+    /// Whether other roots are stored for the slot of `pending_update`
+    fn conflicts_with_stored_roots(
+        &self,
+        deps: Deps,
+        pending_update: &PendingUpdate,
+    ) -> Result<bool, ContractError> {
+        let slot = pending_update.finalized_slot;
+        let header_root = self
+            .state
+            .mapped
+            .header_roots
+            .may_load(deps.storage, slot)?;
+        let execution_state_root = self
+            .state
+            .mapped
+            .execution_state_roots
+            .may_load(deps.storage, slot)?;
+
+        Ok(
+            header_root.is_some_and(|root| root != pending_update.finalized_header_root)
+                || execution_state_root
+                    .is_some_and(|root| root != pending_update.execution_state_root),
+        )
+    }
+}
+
+/// Whether the proven `light_client_update` shows the pending update is wrong:
+/// other roots for the same slot, or a slot beyond what the chain had
+/// finalized by the same or a later attested slot
+fn disproves(light_client_update: &LightClientUpdate, pending_update: &PendingUpdate) -> bool {
+    if light_client_update.finalized_slot == pending_update.finalized_slot {
+        return pending_update.finalized_header_root != light_client_update.finalized_header_root
+            || pending_update.execution_state_root != light_client_update.execution_state_root;
+    }
+
+    light_client_update.attested_slot >= pending_update.attested_slot
+        && light_client_update.finalized_slot < pending_update.finalized_slot
+}
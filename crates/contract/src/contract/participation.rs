@@ -0,0 +1,96 @@
+use super::Contract;
+use crate::error::ContractError;
+use crate::msg::{LightClientUpdate, ParticipationConfig};
+use cosmwasm_std::{Deps, DepsMut, Uint128};
+
+/// Sync committee size of the mainnet preset, also the largest the circuits support
+pub const MAX_SYNC_COMMITTEE_SIZE: u64 = 512;
+
+impl Contract<'_> {
+    pub fn set_participation_config(
+        &self,
+        deps: DepsMut,
+        config: ParticipationConfig,
+    ) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+        self.save_participation_config(deps, config)
+    }
+
+    pub(crate) fn save_participation_config(
+        &self,
+        deps: DepsMut,
+        config: ParticipationConfig,
+    ) -> Result<(), ContractError> {
+        validate_participation_config(&config)?;
+        self.state
+            .participation_config
+            .save(deps.storage, &config)?;
+
+        Ok(())
+    }
+
+    pub fn participation_config(&self, deps: Deps) -> Result<ParticipationConfig, ContractError> {
+        Ok(self
+            .state
+            .participation_config
+            .may_load(deps.storage)?
+            .unwrap_or_default())
+    }
+
+    /// Updates need signatures from at least the configured share of the sync committee
+    pub(crate) fn assert_participation(
+        &self,
+        deps: Deps,
+        light_client_update: &LightClientUpdate,
+    ) -> Result<(), ContractError> {
+        let config = self.participation_config(deps)?;
+        let participation = light_client_update.participation;
+        if participation > config.sync_committee_size {
+            return Err(ContractError::MalformedUpdate {
+                reason: format!(
+                    "participation {participation} exceeds sync committee size {}",
+                    config.sync_committee_size
+                ),
+            });
+        }
+
+        // widened so large thresholds can't overflow
+        let signed = Uint128::from(participation)
+            .checked_mul(Uint128::from(config.threshold_denominator))?;
+        let required = Uint128::from(config.threshold_numerator)
+            .checked_mul(Uint128::from(config.sync_committee_size))?;
+        if signed < required {
+            return Err(ContractError::InsufficientParticipation {
+                participation,
+                threshold_numerator: config.threshold_numerator,
+                threshold_denominator: config.threshold_denominator,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The threshold must be a supermajority of at most the whole committee
+fn validate_participation_config(config: &ParticipationConfig) -> Result<(), ContractError> {
+    let invalid = |reason: &str| {
+        Err(ContractError::InvalidParticipationConfig {
+            reason: reason.to_string(),
+        })
+    };
+
+    if config.sync_committee_size == 0 || config.sync_committee_size > MAX_SYNC_COMMITTEE_SIZE {
+        return invalid("sync committee size must be between 1 and 512");
+    }
+    if config.threshold_denominator == 0 {
+        return invalid("threshold denominator must not be zero");
+    }
+    if config.threshold_numerator > config.threshold_denominator {
+        return invalid("threshold must not exceed 1");
+    }
+    if config.threshold_numerator <= config.threshold_denominator - config.threshold_numerator {
+        return invalid("threshold must be more than 1/2");
+    }
+
+    Ok(())
+}
@@ -1,80 +1,564 @@
 use super::Contract;
+use crate::error::ContractError;
 use crate::eth_utility::NetworkConfig;
-use crate::msg::{VerifyLogEntryRequest, VerifyLogEntryResponse};
-use cosmwasm_std::Deps;
+use crate::msg::{
+    AccountState, ExecutionHeaderInfo, StorageValue, TransactionInfo, VerificationFailure,
+    VerifyAccountRequest, VerifyAccountResponse, VerifyExecutionHeaderRequest,
+    VerifyExecutionHeaderResponse, VerifyLogEntryRequest, VerifyLogEntryResponse,
+    VerifyStorageRequest, VerifyStorageResponse, VerifyTransactionRequest,
+    VerifyTransactionResponse, VerifyWithdrawalRequest, VerifyWithdrawalResponse, Withdrawal,
+};
+use cosmwasm_std::{Deps, Uint256};
 use rlp::Rlp;
 use sha2::{Digest, Sha256};
-use types::{near_keccak256, LogEntry, Receipt, H256};
+use types::{near_keccak256, Account, BlockHeader, LogEntry, Receipt, Transaction, H256, U256};
 
 const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
 const HISTORICAL_ROOTS_LIMIT: u64 = 16777216;
-const STATE_TO_HISTORICAL_G_INDEX: u64 = 27;
+/// Generalized index of the state root in a beacon block header
+const HEADER_STATE_ROOT_GINDEX: u128 = 11;
+/// Beacon state field indices, unchanged since Capella as forks append fields
+const STATE_ROOTS_FIELD: u128 = 6;
+const LATEST_EXECUTION_PAYLOAD_HEADER_FIELD: u128 = 24;
+const HISTORICAL_SUMMARIES_FIELD: u128 = 27;
+const RECEIPTS_ROOT_FIELD: u128 = 3;
+const BLOCK_HASH_FIELD: u128 = 12;
+const WITHDRAWALS_ROOT_FIELD: u128 = 14;
+const MAX_WITHDRAWALS_PER_PAYLOAD: u64 = 16;
 
 impl Contract<'_> {
+    /// Proof failures are reported in the response, so contracts querying
+    /// it can tell a bad proof from an unavailable header root
     pub fn verify_log_entry(
         &self,
         deps: Deps,
         req: VerifyLogEntryRequest,
-    ) -> VerifyLogEntryResponse {
-        let log_entry: LogEntry = rlp::decode(req.log_entry_data.as_slice()).unwrap();
-        let receipt: Receipt = rlp::decode(req.receipt_data.as_slice()).unwrap();
-
+    ) -> Result<VerifyLogEntryResponse, ContractError> {
         // Verify log_entry included in receipt
-        assert_eq!(receipt.logs[req.log_index as usize], log_entry);
+        let log_entry = rlp::decode::<LogEntry>(req.log_entry_data.as_slice());
+        let receipt = rlp::decode::<Receipt>(req.receipt_data.as_slice());
+        let log_included = match (log_entry, receipt) {
+            (Ok(log_entry), Ok(receipt)) => {
+                receipt.logs.get(req.log_index as usize) == Some(&log_entry)
+            }
+            _ => false,
+        };
+        if !log_included {
+            return Ok(log_entry_failure(None, VerificationFailure::LogMismatch));
+        }
         let receipts_root = req.receipts_root;
+        if receipts_root.len() != 32 {
+            return Ok(log_entry_failure(None, VerificationFailure::MalformedProof));
+        }
 
         // Verify receipt included into header
-        let data = verify_trie_proof(
-            receipts_root.clone().into(),
+        let receipt_included = verify_trie_proof(
+            receipts_root.as_slice().try_into()?,
             rlp::encode(&req.receipt_index).to_vec(),
             req.proof,
-        );
-        let verification_result = req.receipt_data == data;
+        )
+        .is_ok_and(|data| data == req.receipt_data);
+        if !receipt_included {
+            return Ok(log_entry_failure(
+                None,
+                VerificationFailure::TrieProofInvalid,
+            ));
+        } else if req.skip_bridge_call {
+            return Ok(VerifyLogEntryResponse {
+                verified: true,
+                src_slot: None,
+                failure_reason: None,
+            });
+        }
 
-        if !verification_result {
-            return VerifyLogEntryResponse { verified: false };
-        } else if verification_result && req.skip_bridge_call {
-            return VerifyLogEntryResponse { verified: true };
+        if !self.slot_range_is_valid(deps, req.src_slot, req.tx_slot)? {
+            return Ok(log_entry_failure(
+                req.src_slot,
+                VerificationFailure::SlotRangeInvalid,
+            ));
         }
 
-        let non_mapped_data = self.state.non_mapped.load(deps.storage).unwrap();
-        let network = non_mapped_data.network;
-        let capella_fork_slot = {
-            let network_config = NetworkConfig::new(&network);
-            network_config.capella_fork_epoch * 32
+        let Some(src_slot) = self.resolve_src_slot(deps, req.src_slot, req.tx_slot)? else {
+            return Ok(log_entry_failure(
+                None,
+                VerificationFailure::HeaderRootUnknown,
+            ));
         };
+        if let Some(failure) = self.missing_header_root_failure(deps, src_slot)? {
+            return Ok(log_entry_failure(Some(src_slot), failure));
+        }
 
-        let historical_list_index = (req.tx_slot - capella_fork_slot) / SLOTS_PER_HISTORICAL_ROOT;
+        let index =
+            self.execution_payload_gindex(deps, src_slot, req.tx_slot, RECEIPTS_ROOT_FIELD)?;
+        if !branch_matches_index(index, &req.receipts_root_proof) {
+            return Ok(log_entry_failure(
+                Some(src_slot),
+                VerificationFailure::MalformedProof,
+            ));
+        }
+        if !self.verify_header_branch(
+            deps,
+            src_slot,
+            receipts_root,
+            index,
+            req.receipts_root_proof,
+        )? {
+            return Ok(log_entry_failure(
+                Some(src_slot),
+                VerificationFailure::BeaconBranchInvalid,
+            ));
+        }
 
-        let mut index: u128 = 0u128;
-        if req.src_slot == req.tx_slot {
-            index = 8 + 3;
-            index = index * 2u128.pow(9) + 387;
-        } else if req.src_slot - req.tx_slot <= SLOTS_PER_HISTORICAL_ROOT {
-            index = 8 + 3;
-            index = index * 2u128.pow(5) + 6;
-            index = index * u128::from(SLOTS_PER_HISTORICAL_ROOT)
-                + u128::from(req.tx_slot % SLOTS_PER_HISTORICAL_ROOT);
-            index = index * 2u128.pow(9) + 387;
-        } else if req.tx_slot < req.src_slot {
-            index = 8 + 3;
-            index = index * 2u128.pow(5) + u128::from(STATE_TO_HISTORICAL_G_INDEX);
+        if !self.root_meets_requirements(deps, src_slot, req.min_participation, req.min_age)? {
+            return Ok(log_entry_failure(
+                Some(src_slot),
+                VerificationFailure::RequirementsNotMet,
+            ));
+        }
+
+        Ok(VerifyLogEntryResponse {
+            verified: true,
+            src_slot: Some(src_slot),
+            failure_reason: None,
+        })
+    }
+
+    /// Verifies a raw transaction in the transactions trie of an execution
+    /// block, whose hash is proven against the header root of `src_slot`
+    pub fn verify_transaction(
+        &self,
+        deps: Deps,
+        req: VerifyTransactionRequest,
+    ) -> Result<VerifyTransactionResponse, ContractError> {
+        let transaction: Transaction = rlp::decode(&req.transaction_data)?;
+
+        let src_slot = self
+            .resolve_src_slot(deps, req.src_slot, req.tx_slot)?
+            .ok_or(ContractError::NoFinalizedSlot {
+                tx_slot: req.tx_slot,
+            })?;
+        let header = self.prove_execution_header(
+            deps,
+            src_slot,
+            req.tx_slot,
+            &req.execution_header,
+            req.block_hash_proof,
+        )?;
+
+        let data = verify_trie_proof(
+            header.transactions_root,
+            rlp::encode(&req.transaction_index).to_vec(),
+            req.proof,
+        )?;
+        if data != req.transaction_data {
+            return Err(invalid_trie_proof(
+                "transaction does not match proven value",
+            ));
+        }
+
+        Ok(VerifyTransactionResponse {
+            src_slot,
+            block_number: header.number,
+            transaction: TransactionInfo {
+                tx_type: transaction.tx_type,
+                chain_id: transaction.chain_id,
+                nonce: transaction.nonce,
+                gas_limit: transaction.gas_limit,
+                to: transaction.to.map(|to| to.0.as_bytes().to_vec()),
+                value: uint256_from_u256(transaction.value),
+                input: transaction.input,
+            },
+        })
+    }
+
+    /// Verifies an execution block header, whose hash is proven against the
+    /// header root of `src_slot`
+    pub fn verify_execution_header(
+        &self,
+        deps: Deps,
+        req: VerifyExecutionHeaderRequest,
+    ) -> Result<VerifyExecutionHeaderResponse, ContractError> {
+        let src_slot = self
+            .resolve_src_slot(deps, req.src_slot, req.tx_slot)?
+            .ok_or(ContractError::NoFinalizedSlot {
+                tx_slot: req.tx_slot,
+            })?;
+        let header = self.prove_execution_header(
+            deps,
+            src_slot,
+            req.tx_slot,
+            &req.execution_header,
+            req.block_hash_proof,
+        )?;
+
+        let root = |root: H256| root.0.as_bytes().to_vec();
+        Ok(VerifyExecutionHeaderResponse {
+            src_slot,
+            header: ExecutionHeaderInfo {
+                block_hash: near_keccak256(&req.execution_header).to_vec(),
+                parent_hash: root(header.parent_hash),
+                number: header.number,
+                timestamp: header.timestamp,
+                base_fee_per_gas: header.base_fee_per_gas,
+                gas_limit: uint256_from_u256(header.gas_limit),
+                gas_used: uint256_from_u256(header.gas_used),
+                state_root: root(header.state_root),
+                transactions_root: root(header.transactions_root),
+                receipts_root: root(header.receipts_root),
+                withdrawals_root: header.withdrawals_root.map(root),
+                parent_beacon_block_root: header.parent_beacon_block_root.map(root),
+            },
+        })
+    }
+
+    /// Verifies a withdrawal at `position` in the withdrawals of the execution
+    /// payload of `tx_slot`, proven against the header root of `src_slot`.
+    /// Failures are reported in the response as for log entries.
+    pub fn verify_withdrawal(
+        &self,
+        deps: Deps,
+        req: VerifyWithdrawalRequest,
+    ) -> Result<VerifyWithdrawalResponse, ContractError> {
+        let leaf = match withdrawal_root(&req.withdrawal) {
+            Some(leaf) if req.position < MAX_WITHDRAWALS_PER_PAYLOAD => leaf,
+            _ => {
+                return Ok(withdrawal_failure(
+                    None,
+                    VerificationFailure::MalformedProof,
+                ))
+            }
+        };
+        if !self.slot_range_is_valid(deps, req.src_slot, req.tx_slot)? {
+            return Ok(withdrawal_failure(
+                req.src_slot,
+                VerificationFailure::SlotRangeInvalid,
+            ));
+        }
+
+        let Some(src_slot) = self.resolve_src_slot(deps, req.src_slot, req.tx_slot)? else {
+            return Ok(withdrawal_failure(
+                None,
+                VerificationFailure::HeaderRootUnknown,
+            ));
+        };
+        if let Some(failure) = self.missing_header_root_failure(deps, src_slot)? {
+            return Ok(withdrawal_failure(Some(src_slot), failure));
+        }
+
+        let mut index =
+            self.execution_payload_gindex(deps, src_slot, req.tx_slot, WITHDRAWALS_ROOT_FIELD)?;
+        // data root of the withdrawals list, then the withdrawal within it
+        index *= 2;
+        index = index * u128::from(MAX_WITHDRAWALS_PER_PAYLOAD) + u128::from(req.position);
+        if !branch_matches_index(index, &req.proof) {
+            return Ok(withdrawal_failure(
+                Some(src_slot),
+                VerificationFailure::MalformedProof,
+            ));
+        }
+        if !self.verify_header_branch(deps, src_slot, leaf, index, req.proof)? {
+            return Ok(withdrawal_failure(
+                Some(src_slot),
+                VerificationFailure::BeaconBranchInvalid,
+            ));
+        }
+
+        Ok(VerifyWithdrawalResponse {
+            verified: true,
+            src_slot: Some(src_slot),
+            failure_reason: None,
+        })
+    }
+
+    /// Verifies an account against the execution state root of `slot`. An
+    /// account proven absent from the state is returned empty.
+    pub fn verify_account(
+        &self,
+        deps: Deps,
+        req: VerifyAccountRequest,
+    ) -> Result<VerifyAccountResponse, ContractError> {
+        Ok(VerifyAccountResponse {
+            account: self.prove_account(deps, req.slot, &req.address, req.account_proof)?,
+        })
+    }
+
+    /// Verifies storage slots of an account against the execution state root
+    /// of `slot`. Slots proven absent from the storage trie are zero.
+    pub fn verify_storage(
+        &self,
+        deps: Deps,
+        req: VerifyStorageRequest,
+    ) -> Result<VerifyStorageResponse, ContractError> {
+        let account = self.prove_account(deps, req.slot, &req.address, req.account_proof)?;
+
+        let values = req
+            .storage_proofs
+            .into_iter()
+            .map(|storage_proof| {
+                if storage_proof.key.len() != 32 {
+                    return Err(malformed_proof_request("storage key must be 32 bytes"));
+                }
+                let value: Vec<u8> = match prove_trie_value(
+                    account.storage_root.as_slice().try_into()?,
+                    near_keccak256(&storage_proof.key).to_vec(),
+                    storage_proof.proof,
+                )? {
+                    Some(data) => rlp::decode(&data)?,
+                    None => vec![],
+                };
+                if value.len() > 32 {
+                    return Err(invalid_trie_proof("storage value longer than 32 bytes"));
+                }
+
+                Ok(StorageValue {
+                    key: storage_proof.key,
+                    value: uint256_from_be_slice(&value),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(VerifyStorageResponse { account, values })
+    }
+
+    fn prove_account(
+        &self,
+        deps: Deps,
+        slot: u64,
+        address: &[u8],
+        account_proof: Vec<Vec<u8>>,
+    ) -> Result<AccountState, ContractError> {
+        if address.len() != 20 {
+            return Err(malformed_proof_request("address must be 20 bytes"));
+        }
+        let state_root = self
+            .execution_state_root(deps, slot)?
+            .execution_state_root
+            .ok_or(ContractError::UnknownExecutionStateRoot { slot })?;
+        if state_root.len() != 32 {
+            return Err(malformed_proof_request("stored state root is not 32 bytes"));
+        }
+
+        let data = prove_trie_value(
+            state_root.try_into()?,
+            near_keccak256(address).to_vec(),
+            account_proof,
+        )?;
+        let Some(data) = data else {
+            return Ok(AccountState {
+                nonce: 0,
+                balance: Uint256::zero(),
+                storage_root: empty_trie_root(),
+                code_hash: near_keccak256(&[]).to_vec(),
+            });
+        };
+        let account: Account = rlp::decode(&data)?;
+
+        Ok(AccountState {
+            nonce: account.nonce,
+            balance: uint256_from_u256(account.balance),
+            storage_root: account.storage_root.0.as_bytes().to_vec(),
+            code_hash: account.code_hash.0.as_bytes().to_vec(),
+        })
+    }
+
+    /// `src_slot` if set, otherwise the nearest stored slot at or after `tx_slot`
+    fn resolve_src_slot(
+        &self,
+        deps: Deps,
+        src_slot: Option<u64>,
+        tx_slot: u64,
+    ) -> Result<Option<u64>, ContractError> {
+        match src_slot {
+            Some(src_slot) => Ok(Some(src_slot)),
+            None => Ok(self.nearest_finalized_slot(deps, Some(tx_slot), None)?.slot),
+        }
+    }
+
+    /// Whether the block at `tx_slot` is after Capella and can be proven
+    /// against the header root of `src_slot`, or of the head if unset
+    fn slot_range_is_valid(
+        &self,
+        deps: Deps,
+        src_slot: Option<u64>,
+        tx_slot: u64,
+    ) -> Result<bool, ContractError> {
+        let max_slot = match src_slot {
+            Some(src_slot) => src_slot,
+            None => self.head(deps)?.head,
+        };
+
+        Ok(self.capella_fork_slot(deps)? <= tx_slot && tx_slot <= max_slot)
+    }
+
+    /// Why proofs can't be checked against the header root of `src_slot`,
+    /// `None` if it is stored
+    fn missing_header_root_failure(
+        &self,
+        deps: Deps,
+        src_slot: u64,
+    ) -> Result<Option<VerificationFailure>, ContractError> {
+        if self.header_root(deps, src_slot)?.header_root.is_some() {
+            return Ok(None);
+        }
+
+        Ok(Some(if self.outside_retention_window(deps, src_slot)? {
+            VerificationFailure::SlotPruned
+        } else {
+            VerificationFailure::HeaderRootUnknown
+        }))
+    }
+
+    fn capella_fork_slot(&self, deps: Deps) -> Result<u64, ContractError> {
+        let network = self.state.non_mapped.load(deps.storage)?.network;
+        Ok(NetworkConfig::new(&network).capella_fork_epoch * 32)
+    }
+
+    /// Decodes the rlp encoded execution block header of `tx_slot` after
+    /// checking it hashes to the block hash proven against the header root of `src_slot`
+    fn prove_execution_header(
+        &self,
+        deps: Deps,
+        src_slot: u64,
+        tx_slot: u64,
+        execution_header: &[u8],
+        block_hash_proof: Vec<Vec<u8>>,
+    ) -> Result<BlockHeader, ContractError> {
+        if self.header_root(deps, src_slot)?.header_root.is_none() {
+            return Err(ContractError::UnknownHeaderRoot { slot: src_slot });
+        }
+        let block_hash = near_keccak256(execution_header).to_vec();
+        if !self.verify_execution_payload_field(
+            deps,
+            src_slot,
+            tx_slot,
+            BLOCK_HASH_FIELD,
+            block_hash,
+            block_hash_proof,
+        )? {
+            return Err(ContractError::InvalidBeaconBranch { slot: src_slot });
+        }
+
+        Ok(rlp::decode(execution_header)?)
+    }
+
+    /// Whether `leaf` is the execution payload header field `field` of the
+    /// block at `tx_slot`, proven by `branch` against the header root of `src_slot`
+    fn verify_execution_payload_field(
+        &self,
+        deps: Deps,
+        src_slot: u64,
+        tx_slot: u64,
+        field: u128,
+        leaf: Vec<u8>,
+        branch: Vec<Vec<u8>>,
+    ) -> Result<bool, ContractError> {
+        let index = self.execution_payload_gindex(deps, src_slot, tx_slot, field)?;
+        self.verify_header_branch(deps, src_slot, leaf, index, branch)
+    }
+
+    /// Generalized index of the execution payload header field `field` of the
+    /// block at `tx_slot` below the header root of `src_slot`. The path runs
+    /// through beacon states, whose tree depths depend on the fork of their slot.
+    fn execution_payload_gindex(
+        &self,
+        deps: Deps,
+        src_slot: u64,
+        tx_slot: u64,
+        field: u128,
+    ) -> Result<u128, ContractError> {
+        if tx_slot > src_slot {
+            return Err(ContractError::InvalidSlotRange { src_slot, tx_slot });
+        }
+
+        let historical_list_index = tx_slot
+            .checked_sub(self.capella_fork_slot(deps)?)
+            .ok_or(ContractError::InvalidSlotRange { src_slot, tx_slot })?
+            / SLOTS_PER_HISTORICAL_ROOT;
+
+        let network = self.state.non_mapped.load(deps.storage)?.network;
+        let config = NetworkConfig::new(&network);
+        let src_state_depth = config.beacon_state_depth(src_slot);
+
+        // state root of the block at `tx_slot`
+        let mut index = HEADER_STATE_ROOT_GINDEX;
+        if src_slot - tx_slot > SLOTS_PER_HISTORICAL_ROOT {
+            index = index * 2u128.pow(src_state_depth) + HISTORICAL_SUMMARIES_FIELD;
             index *= 2;
             index = index * u128::from(HISTORICAL_ROOTS_LIMIT) + u128::from(historical_list_index);
             index = index * 2 + 1;
             index = index * u128::from(SLOTS_PER_HISTORICAL_ROOT)
-                + u128::from(req.tx_slot % SLOTS_PER_HISTORICAL_ROOT);
-            index = index * 2u128.pow(9) + 387;
+                + u128::from(tx_slot % SLOTS_PER_HISTORICAL_ROOT);
+        } else if src_slot > tx_slot {
+            index = index * 2u128.pow(src_state_depth) + STATE_ROOTS_FIELD;
+            index = index * u128::from(SLOTS_PER_HISTORICAL_ROOT)
+                + u128::from(tx_slot % SLOTS_PER_HISTORICAL_ROOT);
         }
 
-        let expected_header_root =
-            restore_merkle_root(receipts_root, index, req.receipts_root_proof);
+        index = index * 2u128.pow(config.beacon_state_depth(tx_slot))
+            + LATEST_EXECUTION_PAYLOAD_HEADER_FIELD;
+        index = index * 2u128.pow(config.execution_payload_header_depth(tx_slot)) + field;
 
-        if self.header_root(deps, req.src_slot).header_root != Some(expected_header_root) {
-            return VerifyLogEntryResponse { verified: false };
+        Ok(index)
+    }
+
+    /// Whether `branch` proves `leaf` at `index` against the header root of `slot`
+    fn verify_header_branch(
+        &self,
+        deps: Deps,
+        slot: u64,
+        leaf: Vec<u8>,
+        index: u128,
+        branch: Vec<Vec<u8>>,
+    ) -> Result<bool, ContractError> {
+        if !branch_matches_index(index, &branch) {
+            return Ok(false);
+        }
+        let expected_header_root = restore_merkle_root(leaf, index, branch);
+
+        Ok(self.header_root(deps, slot)?.header_root == Some(expected_header_root))
+    }
+
+    /// Whether the root stored for `slot` was attested by enough of the sync
+    /// committee and stored at least `min_age` seconds ago. Roots without
+    /// provenance only meet empty requirements.
+    fn root_meets_requirements(
+        &self,
+        deps: Deps,
+        slot: u64,
+        min_participation: Option<u64>,
+        min_age: Option<u64>,
+    ) -> Result<bool, ContractError> {
+        if min_participation.is_none() && min_age.is_none() {
+            return Ok(true);
+        }
+        let Some(info) = self
+            .state
+            .mapped
+            .finalized_header_infos
+            .may_load(deps.storage, slot)?
+        else {
+            return Ok(false);
+        };
+
+        if let Some(min_participation) = min_participation {
+            if info.participation.unwrap_or_default() < min_participation {
+                return Ok(false);
+            }
+        }
+        if let Some(min_age) = min_age {
+            let age = self
+                .ctx
+                .env
+                .block
+                .time
+                .seconds()
+                .saturating_sub(info.block_time);
+            if age < min_age {
+                return Ok(false);
+            }
         }
 
-        VerifyLogEntryResponse { verified: true }
+        Ok(true)
     }
 }
 
@@ -92,7 +576,27 @@ impl Contract<'_> {
 /// Article:       https://medium.com/@ouvrard.pierre.alain/merkle-proof-verification-for-ethereum-patricia-tree-48f29658eec
 /// Python impl:   https://gist.github.com/mfornet/0ff283274c0162f1cca45966bccf69ee
 ///
-fn verify_trie_proof(expected_root: H256, key: Vec<u8>, proof: Vec<Vec<u8>>) -> Vec<u8> {
+/// Value stored under `key`, fails if the proof shows the key is absent
+fn verify_trie_proof(
+    expected_root: H256,
+    key: Vec<u8>,
+    proof: Vec<Vec<u8>>,
+) -> Result<Vec<u8>, ContractError> {
+    prove_trie_value(expected_root, key, proof)?
+        .ok_or_else(|| invalid_trie_proof("key is not in the trie"))
+}
+
+/// Value stored under `key`, or `None` if the proof shows the key is absent
+fn prove_trie_value(
+    expected_root: H256,
+    key: Vec<u8>,
+    proof: Vec<Vec<u8>>,
+) -> Result<Option<Vec<u8>>, ContractError> {
+    // nodes are only needed to prove absence from a non-empty trie
+    if proof.is_empty() && expected_root.0.as_bytes() == empty_trie_root() {
+        return Ok(None);
+    }
+
     let mut actual_key = vec![];
     for el in key {
         actual_key.push(el / 16);
@@ -107,28 +611,50 @@ fn _verify_trie_proof(
     proof: &Vec<Vec<u8>>,
     key_index: usize,
     proof_index: usize,
-) -> Vec<u8> {
-    let node = &proof[proof_index];
+) -> Result<Option<Vec<u8>>, ContractError> {
+    let node = proof
+        .get(proof_index)
+        .ok_or_else(|| invalid_trie_proof("proof is missing nodes"))?;
 
     if key_index == 0 {
         // trie root is always a hash
-        assert_eq!(near_keccak256(node), expected_root.as_slice());
+        if near_keccak256(node) != expected_root.as_slice() {
+            return Err(invalid_trie_proof("root node hash mismatch"));
+        }
     } else if node.len() < 32 {
         // if rlp < 32 bytes, then it is not hashed
-        assert_eq!(node.as_slice(), expected_root);
-    } else {
-        assert_eq!(near_keccak256(node), expected_root.as_slice());
+        if node.as_slice() != expected_root {
+            return Err(invalid_trie_proof("inline node mismatch"));
+        }
+    } else if near_keccak256(node) != expected_root.as_slice() {
+        return Err(invalid_trie_proof("node hash mismatch"));
     }
+    let is_last = proof_index + 1 == proof.len();
 
     let node = Rlp::new(node.as_slice());
 
-    if node.iter().count() == 17 {
+    if key_index == 0 && node.is_empty() {
+        // Empty trie
+        if !is_last {
+            return Err(invalid_trie_proof("unexpected nodes after empty root"));
+        }
+        Ok(None)
+    } else if node.iter().count() == 17 {
         // Branch node
         if key_index == key.len() {
-            assert_eq!(proof_index + 1, proof.len());
-            get_vec(&node, 16)
+            if !is_last {
+                return Err(invalid_trie_proof("unexpected nodes after branch value"));
+            }
+            let value = get_vec(&node, 16)?;
+            Ok((!value.is_empty()).then_some(value))
         } else {
-            let new_expected_root = get_vec(&node, key[key_index] as usize);
+            let new_expected_root = get_vec(&node, key[key_index] as usize)?;
+            if new_expected_root.is_empty() {
+                if !is_last {
+                    return Err(invalid_trie_proof("unexpected nodes after empty branch"));
+                }
+                return Ok(None);
+            }
             _verify_trie_proof(
                 new_expected_root,
                 key,
@@ -139,12 +665,21 @@ fn _verify_trie_proof(
         }
     } else {
         // Leaf or extension node
-        assert_eq!(node.iter().count(), 2);
-        let path_u8 = get_vec(&node, 0);
+        if node.iter().count() != 2 {
+            return Err(invalid_trie_proof(
+                "node is neither branch, leaf nor extension",
+            ));
+        }
+        let path_u8 = get_vec(&node, 0)?;
         // Extract first nibble
-        let head = path_u8[0] / 16;
-        // assert!(0 <= head); is implicit because of type limits
-        assert!(head <= 3);
+        let head = path_u8
+            .first()
+            .ok_or_else(|| invalid_trie_proof("empty node path"))?
+            / 16;
+        // 0 <= head is implicit because of type limits
+        if head > 3 {
+            return Err(invalid_trie_proof("invalid node path prefix"));
+        }
 
         // Extract path
         let mut path = vec![];
@@ -155,16 +690,29 @@ fn _verify_trie_proof(
             path.push(val / 16);
             path.push(val % 16);
         }
-        assert_eq!(path.as_slice(), &key[key_index..key_index + path.len()]);
+        let is_leaf = head >= 2;
+        let key_rest = &key[key_index..];
+        let matches = if is_leaf {
+            key_rest == path.as_slice()
+        } else {
+            key_rest.starts_with(&path)
+        };
+        if !matches {
+            // the key diverges from the only path the trie has here
+            if !is_last {
+                return Err(invalid_trie_proof("unexpected nodes after diverging path"));
+            }
+            return Ok(None);
+        }
 
-        if head >= 2 {
-            // Leaf node
-            assert_eq!(proof_index + 1, proof.len());
-            assert_eq!(key_index + path.len(), key.len());
-            get_vec(&node, 1)
+        if is_leaf {
+            if !is_last {
+                return Err(invalid_trie_proof("unexpected nodes after leaf"));
+            }
+            Ok(Some(get_vec(&node, 1)?))
         } else {
             // Extension node
-            let new_expected_root = get_vec(&node, 1);
+            let new_expected_root = get_vec(&node, 1)?;
             _verify_trie_proof(
                 new_expected_root,
                 key,
@@ -176,10 +724,97 @@ fn _verify_trie_proof(
     }
 }
 
+/// Root of a trie without any entries, `keccak256(rlp(""))`
+fn empty_trie_root() -> Vec<u8> {
+    near_keccak256(&rlp::NULL_RLP).to_vec()
+}
+
 /// Get element at position `pos` from rlp encoded data,
 /// and decode it as vector of bytes
-fn get_vec(data: &Rlp, pos: usize) -> Vec<u8> {
-    data.at(pos).unwrap().as_val::<Vec<u8>>().unwrap()
+fn get_vec(data: &Rlp, pos: usize) -> Result<Vec<u8>, ContractError> {
+    Ok(data.at(pos)?.as_val::<Vec<u8>>()?)
+}
+
+fn malformed_proof_request(reason: &str) -> ContractError {
+    ContractError::MalformedProofRequest {
+        reason: reason.to_string(),
+    }
+}
+
+fn log_entry_failure(src_slot: Option<u64>, reason: VerificationFailure) -> VerifyLogEntryResponse {
+    VerifyLogEntryResponse {
+        verified: false,
+        src_slot,
+        failure_reason: Some(reason),
+    }
+}
+
+fn withdrawal_failure(
+    src_slot: Option<u64>,
+    reason: VerificationFailure,
+) -> VerifyWithdrawalResponse {
+    VerifyWithdrawalResponse {
+        verified: false,
+        src_slot,
+        failure_reason: Some(reason),
+    }
+}
+
+fn uint256_from_u256(value: U256) -> Uint256 {
+    let mut bytes = [0u8; 32];
+    value.0.to_big_endian(&mut bytes);
+    Uint256::from_be_bytes(bytes)
+}
+
+/// Big endian value of at most 32 bytes, left padded with zeros
+fn uint256_from_be_slice(value: &[u8]) -> Uint256 {
+    let mut bytes = [0u8; 32];
+    bytes[32 - value.len()..].copy_from_slice(value);
+    Uint256::from_be_bytes(bytes)
+}
+
+/// SSZ hash tree root of a withdrawal, its four fields fill one chunk each
+/// Hash tree root of `withdrawal`, `None` if its address isn't 20 bytes
+fn withdrawal_root(withdrawal: &Withdrawal) -> Option<Vec<u8>> {
+    if withdrawal.address.len() != 20 {
+        return None;
+    }
+    let chunk = |bytes: &[u8]| {
+        let mut chunk = [0u8; 32];
+        chunk[..bytes.len()].copy_from_slice(bytes);
+        chunk
+    };
+    let hash = |left: [u8; 32], right: [u8; 32]| -> [u8; 32] {
+        Sha256::digest([left, right].concat()).into()
+    };
+
+    Some(
+        hash(
+            hash(
+                chunk(&withdrawal.index.to_le_bytes()),
+                chunk(&withdrawal.validator_index.to_le_bytes()),
+            ),
+            hash(
+                chunk(&withdrawal.address),
+                chunk(&withdrawal.amount.to_le_bytes()),
+            ),
+        )
+        .to_vec(),
+    )
+}
+
+fn invalid_trie_proof(reason: &str) -> ContractError {
+    ContractError::InvalidTrieProof {
+        reason: reason.to_string(),
+    }
+}
+
+/// Whether `branch` holds a 32 byte sibling for each level above `index`, so it
+/// can't prove a leaf at another position of the tree
+fn branch_matches_index(index: u128, branch: &[Vec<u8>]) -> bool {
+    index != 0
+        && branch.len() == index.ilog2() as usize
+        && branch.iter().all(|sibling| sibling.len() == 32)
 }
 
 fn restore_merkle_root(leaf: Vec<u8>, mut index: u128, branch: Vec<Vec<u8>>) -> Vec<u8> {
@@ -188,10 +823,10 @@ fn restore_merkle_root(leaf: Vec<u8>, mut index: u128, branch: Vec<Vec<u8>>) ->
         let mut hasher = Sha256::new();
 
         if index & 1 == 0 {
-            hasher.update(&[&value, b.as_slice()].concat());
+            hasher.update([&value, b.as_slice()].concat());
             value = hasher.finalize().to_vec();
         } else {
-            hasher.update(&[b.as_slice(), &value].concat());
+            hasher.update([b.as_slice(), &value].concat());
             value = hasher.finalize().to_vec();
         }
         index >>= 1;
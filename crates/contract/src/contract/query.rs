@@ -1,46 +1,417 @@
 use super::Contract;
+use crate::error::ContractError;
+use crate::eth_utility::{compute_sync_committee_period, NetworkConfig};
 use crate::msg::{
-    ExecutionStateRootResponse, HeadResponse, HeaderRootResponse, SyncCommitteePoseidonHashResponse,
+    AdminResponse, Circuit, ConfigResponse, ExecutionStateRootEntry, ExecutionStateRootResponse,
+    ExecutionStateRootsResponse, FinalizedHeaderInfoResponse, HeadResponse, HeaderRootEntry,
+    HeaderRootResponse, HeaderRootsResponse, NearestFinalizedSlotResponse,
+    OldestRetainedSlotResponse, OptimisticConfigResponse, ParticipationConfigResponse,
+    PendingUpdateResponse, RangeOrder, RebootstrapHistoryResponse, RelayersResponse,
+    RewardBalanceResponse, RewardConfigResponse, StatusResponse, SubscribersResponse,
+    SyncCommitteePoseidonHashEntry, SyncCommitteePoseidonHashResponse,
+    SyncCommitteePoseidonHashesResponse, VerifyingKeyInfo, VerifyingKeysResponse,
 };
-use cosmwasm_std::Deps;
+use cosmwasm_std::{Coin, Deps, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+
+/// Entries returned by range queries without a limit
+const DEFAULT_RANGE_LIMIT: u32 = 10;
+/// Max entries returned by range queries
+const MAX_RANGE_LIMIT: u32 = 100;
 
 impl Contract<'_> {
-    pub fn head(&self, deps: Deps) -> HeadResponse {
-        let non_mapped_state = self.state.non_mapped.load(deps.storage).unwrap();
-        HeadResponse {
+    pub fn head(&self, deps: Deps) -> Result<HeadResponse, ContractError> {
+        let non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        Ok(HeadResponse {
             head: non_mapped_state.head_slot,
-        }
+            paused: self.is_paused(deps)?,
+        })
     }
 
-    pub fn header_root(&self, deps: Deps, slot: u64) -> HeaderRootResponse {
-        HeaderRootResponse {
-            header_root: self.state.mapped.header_roots.load(deps.storage, slot).ok(),
-        }
+    pub fn status(
+        &self,
+        deps: Deps,
+        max_lag: Option<u64>,
+    ) -> Result<StatusResponse, ContractError> {
+        let non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        let head_slot = non_mapped_state.head_slot;
+        let head_timestamp =
+            NetworkConfig::new(&non_mapped_state.network).compute_timestamp_at_slot(head_slot);
+        let lag = self
+            .ctx
+            .env
+            .block
+            .time
+            .seconds()
+            .saturating_sub(head_timestamp);
+        let current_period = compute_sync_committee_period(head_slot);
+
+        Ok(StatusResponse {
+            head_slot,
+            head_timestamp,
+            lag,
+            current_period,
+            next_sync_committee_known: self
+                .state
+                .mapped
+                .sync_committee_poseidon_hashes
+                .has(deps.storage, current_period + 1),
+            is_stale: max_lag.map(|max_lag| lag > max_lag),
+        })
+    }
+
+    pub fn config(&self, deps: Deps) -> Result<ConfigResponse, ContractError> {
+        let non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        let network_config = NetworkConfig::new(&non_mapped_state.network);
+        let active_vkey = |circuit| {
+            self.vkey_at_slot(deps, &circuit, non_mapped_state.head_slot)
+                .map(|vkey_version| VerifyingKeyInfo {
+                    version: vkey_version.version,
+                    activation_slot: vkey_version.activation_slot,
+                    vkey_hash: vkey_version.vkey_hash,
+                })
+        };
+
+        Ok(ConfigResponse {
+            admin: non_mapped_state.admin.clone(),
+            network: non_mapped_state.network.to_string(),
+            genesis_time: network_config.genesis_time,
+            genesis_validators_root: network_config.genesis_validators_root.to_vec(),
+            bellatrix_fork_version: network_config.bellatrix_fork_version.to_vec(),
+            bellatrix_fork_epoch: network_config.bellatrix_fork_epoch,
+            capella_fork_version: network_config.capella_fork_version.to_vec(),
+            capella_fork_epoch: network_config.capella_fork_epoch,
+            participation: self.participation_config(deps)?,
+            lc_update_vkey: active_vkey(Circuit::LcUpdate)?,
+            sc_update_vkey: active_vkey(Circuit::ScUpdate)?,
+        })
+    }
+
+    pub fn admin(&self, deps: Deps) -> Result<AdminResponse, ContractError> {
+        let non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        Ok(AdminResponse {
+            admin: non_mapped_state.admin,
+            pending_admin: self.state.pending_admin.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn relayers(&self, deps: Deps) -> Result<RelayersResponse, ContractError> {
+        let relayers = self
+            .state
+            .mapped
+            .relayers
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        Ok(RelayersResponse {
+            allowlist_enabled: self.is_relayer_allowlist_enabled(deps)?,
+            relayers,
+        })
+    }
+
+    pub fn reward_config(&self, deps: Deps) -> Result<RewardConfigResponse, ContractError> {
+        Ok(RewardConfigResponse {
+            reward: self.state.reward.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn reward_balance(&self, deps: Deps) -> Result<RewardBalanceResponse, ContractError> {
+        let balance = match self.state.reward.may_load(deps.storage)? {
+            Some(reward) => Some(Coin {
+                denom: reward.denom,
+                amount: self
+                    .state
+                    .reward_pool
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+            }),
+            None => None,
+        };
+
+        Ok(RewardBalanceResponse { balance })
+    }
+
+    pub fn oldest_retained_slot(
+        &self,
+        deps: Deps,
+    ) -> Result<OldestRetainedSlotResponse, ContractError> {
+        let retained_from = self.retained_from(deps)?.map(Bound::inclusive);
+        let slot = self
+            .state
+            .mapped
+            .header_roots
+            .keys(deps.storage, retained_from, None, Order::Ascending)
+            .next()
+            .transpose()?;
+
+        Ok(OldestRetainedSlotResponse {
+            slot,
+            retention_slots: self.state.retention_slots.may_load(deps.storage)?,
+        })
     }
 
-    pub fn execution_state_root(&self, deps: Deps, slot: u64) -> ExecutionStateRootResponse {
-        ExecutionStateRootResponse {
-            execution_state_root: self
+    pub fn participation(&self, deps: Deps) -> Result<ParticipationConfigResponse, ContractError> {
+        Ok(ParticipationConfigResponse {
+            config: self.participation_config(deps)?,
+        })
+    }
+
+    pub fn optimistic_config(&self, deps: Deps) -> Result<OptimisticConfigResponse, ContractError> {
+        Ok(OptimisticConfigResponse {
+            config: self.state.optimistic_config.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn pending_update(
+        &self,
+        deps: Deps,
+        finalized_slot: u64,
+    ) -> Result<PendingUpdateResponse, ContractError> {
+        Ok(PendingUpdateResponse {
+            pending_update: self
                 .state
                 .mapped
-                .execution_state_roots
-                .load(deps.storage, slot)
-                .ok(),
-        }
+                .pending_updates
+                .may_load(deps.storage, finalized_slot)?,
+        })
+    }
+
+    pub fn header_root(&self, deps: Deps, slot: u64) -> Result<HeaderRootResponse, ContractError> {
+        Ok(HeaderRootResponse {
+            header_root: self.load_retained(deps, &self.state.mapped.header_roots, slot)?,
+            pending_header_root: self
+                .state
+                .mapped
+                .pending_updates
+                .may_load(deps.storage, slot)?
+                .map(|pending_update| pending_update.finalized_header_root),
+        })
+    }
+
+    pub fn finalized_header_info(
+        &self,
+        deps: Deps,
+        slot: u64,
+    ) -> Result<FinalizedHeaderInfoResponse, ContractError> {
+        Ok(FinalizedHeaderInfoResponse {
+            info: self.load_retained(deps, &self.state.mapped.finalized_header_infos, slot)?,
+        })
+    }
+
+    pub fn nearest_finalized_slot(
+        &self,
+        deps: Deps,
+        at_or_after: Option<u64>,
+        at_or_before: Option<u64>,
+    ) -> Result<NearestFinalizedSlotResponse, ContractError> {
+        let retained_from = self.retained_from(deps)?;
+        let (min, max, order) = match (at_or_after, at_or_before) {
+            (Some(slot), None) => (
+                Some(Bound::inclusive(
+                    retained_from.map_or(slot, |from| slot.max(from)),
+                )),
+                None,
+                Order::Ascending,
+            ),
+            (None, Some(slot)) => (
+                retained_from.map(Bound::inclusive),
+                Some(Bound::inclusive(slot)),
+                Order::Descending,
+            ),
+            _ => return Err(ContractError::InvalidSlotBound {}),
+        };
+        let slot = self
+            .state
+            .mapped
+            .header_roots
+            .keys(deps.storage, min, max, order)
+            .next()
+            .transpose()?;
+
+        Ok(NearestFinalizedSlotResponse { slot })
+    }
+
+    pub fn execution_state_root(
+        &self,
+        deps: Deps,
+        slot: u64,
+    ) -> Result<ExecutionStateRootResponse, ContractError> {
+        Ok(ExecutionStateRootResponse {
+            execution_state_root: self.load_retained(
+                deps,
+                &self.state.mapped.execution_state_roots,
+                slot,
+            )?,
+        })
     }
 
     pub fn sync_committee_poseidon_hash(
         &self,
         deps: Deps,
         period: u64,
-    ) -> SyncCommitteePoseidonHashResponse {
-        SyncCommitteePoseidonHashResponse {
+    ) -> Result<SyncCommitteePoseidonHashResponse, ContractError> {
+        Ok(SyncCommitteePoseidonHashResponse {
             sync_committee_poseidon_hash: self
                 .state
                 .mapped
                 .sync_committee_poseidon_hashes
-                .load(deps.storage, period)
-                .ok(),
-        }
+                .may_load(deps.storage, period)?,
+        })
     }
+
+    pub fn header_roots(
+        &self,
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<HeaderRootsResponse, ContractError> {
+        let header_roots = range_after(
+            deps.storage,
+            &self.state.mapped.header_roots,
+            self.retained_from(deps)?,
+            start_after,
+            limit,
+            order,
+        )?
+        .into_iter()
+        .map(|(slot, header_root)| HeaderRootEntry { slot, header_root })
+        .collect();
+
+        Ok(HeaderRootsResponse { header_roots })
+    }
+
+    pub fn execution_state_roots(
+        &self,
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<ExecutionStateRootsResponse, ContractError> {
+        let execution_state_roots = range_after(
+            deps.storage,
+            &self.state.mapped.execution_state_roots,
+            self.retained_from(deps)?,
+            start_after,
+            limit,
+            order,
+        )?
+        .into_iter()
+        .map(|(slot, execution_state_root)| ExecutionStateRootEntry {
+            slot,
+            execution_state_root,
+        })
+        .collect();
+
+        Ok(ExecutionStateRootsResponse {
+            execution_state_roots,
+        })
+    }
+
+    pub fn sync_committee_poseidon_hashes(
+        &self,
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<SyncCommitteePoseidonHashesResponse, ContractError> {
+        let sync_committee_poseidon_hashes = range_after(
+            deps.storage,
+            &self.state.mapped.sync_committee_poseidon_hashes,
+            None,
+            start_after,
+            limit,
+            order,
+        )?
+        .into_iter()
+        .map(
+            |(period, sync_committee_poseidon_hash)| SyncCommitteePoseidonHashEntry {
+                period,
+                sync_committee_poseidon_hash,
+            },
+        )
+        .collect();
+
+        Ok(SyncCommitteePoseidonHashesResponse {
+            sync_committee_poseidon_hashes,
+        })
+    }
+
+    pub fn verifying_keys(
+        &self,
+        deps: Deps,
+        circuit: Circuit,
+    ) -> Result<VerifyingKeysResponse, ContractError> {
+        let versions = self
+            .state
+            .mapped
+            .vkeys(&circuit)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(_, vkey_version)| VerifyingKeyInfo {
+                    version: vkey_version.version,
+                    activation_slot: vkey_version.activation_slot,
+                    vkey_hash: vkey_version.vkey_hash,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(VerifyingKeysResponse { versions })
+    }
+
+    pub fn rebootstrap_history(
+        &self,
+        deps: Deps,
+    ) -> Result<RebootstrapHistoryResponse, ContractError> {
+        let records = self
+            .state
+            .mapped
+            .rebootstraps
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, record)| record))
+            .collect::<StdResult<_>>()?;
+
+        Ok(RebootstrapHistoryResponse { records })
+    }
+
+    pub fn subscribers(&self, deps: Deps) -> Result<SubscribersResponse, ContractError> {
+        Ok(SubscribersResponse {
+            subscribers: self
+                .subscriptions(deps)?
+                .into_iter()
+                .map(|(_, subscriber)| subscriber)
+                .collect(),
+            limits: self.subscription_limits(deps)?,
+        })
+    }
+}
+
+/// Up to `limit` entries of `map` following the key `start_after` in `order`,
+/// skipping keys before `retained_from`
+fn range_after(
+    storage: &dyn Storage,
+    map: &Map<u64, Vec<u8>>,
+    retained_from: Option<u64>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order: Option<RangeOrder>,
+) -> StdResult<Vec<(u64, Vec<u8>)>> {
+    let limit = limit.unwrap_or(DEFAULT_RANGE_LIMIT).min(MAX_RANGE_LIMIT) as usize;
+    let (min, max, order) = match order.unwrap_or(RangeOrder::Ascending) {
+        RangeOrder::Ascending => {
+            let min = match (start_after, retained_from) {
+                (Some(after), Some(from)) if after < from => Some(Bound::inclusive(from)),
+                (Some(after), _) => Some(Bound::exclusive(after)),
+                (None, from) => from.map(Bound::inclusive),
+            };
+            (min, None, Order::Ascending)
+        }
+        RangeOrder::Descending => (
+            retained_from.map(Bound::inclusive),
+            start_after.map(Bound::exclusive),
+            Order::Descending,
+        ),
+    };
+
+    map.range(storage, min, max, order).take(limit).collect()
 }
@@ -0,0 +1,120 @@
+use super::Contract;
+use crate::error::ContractError;
+use crate::eth_utility::compute_sync_committee_period;
+use crate::events::LightClientEvent;
+use crate::msg::{Checkpoint, RebootstrapRecord};
+use cosmwasm_std::{DepsMut, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+impl Contract<'_> {
+    /// Re-seeds the light client from a trusted checkpoint. Roots before the
+    /// checkpoint are kept, while roots after its slot and sync committees
+    /// after its period are dropped, as whatever is being recovered from may
+    /// have accepted them. Pending updates are dropped too, those up to the
+    /// checkpoint can't be finalized anymore, and their bonds are refunded as
+    /// nothing proved them wrong. Each re-bootstrap is kept in
+    /// the audit trail and emitted as a `Rebootstrapped` event.
+    pub fn rebootstrap(&self, deps: DepsMut, checkpoint: Checkpoint) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let mut non_mapped_state = self.state.non_mapped.load(deps.storage)?;
+        let previous_head_slot = non_mapped_state.head_slot;
+        non_mapped_state.head_slot = checkpoint.head_slot;
+        self.state
+            .non_mapped
+            .save(deps.storage, &non_mapped_state)?;
+
+        // the checkpoint is trusted over whatever was stored for its slot
+        self.state.mapped.header_roots.save(
+            deps.storage,
+            checkpoint.head_slot,
+            &checkpoint.header_root,
+        )?;
+        self.state.mapped.execution_state_roots.save(
+            deps.storage,
+            checkpoint.head_slot,
+            &checkpoint.execution_state_root,
+        )?;
+        self.state.mapped.finalized_header_infos.save(
+            deps.storage,
+            checkpoint.head_slot,
+            &self.header_provenance(None, None),
+        )?;
+        let period = compute_sync_committee_period(checkpoint.head_slot);
+        self.state.mapped.sync_committee_poseidon_hashes.save(
+            deps.storage,
+            period,
+            &checkpoint.sync_committee_poseidon_hash,
+        )?;
+
+        let mapped = &self.state.mapped;
+        remove_after(deps.storage, &mapped.header_roots, checkpoint.head_slot)?;
+        remove_after(
+            deps.storage,
+            &mapped.execution_state_roots,
+            checkpoint.head_slot,
+        )?;
+        remove_after(
+            deps.storage,
+            &mapped.finalized_header_infos,
+            checkpoint.head_slot,
+        )?;
+        let dropped_updates = mapped
+            .pending_updates
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (slot, pending_update) in dropped_updates {
+            mapped.pending_updates.remove(deps.storage, slot);
+            self.send(&pending_update.relayer, pending_update.bond);
+        }
+        remove_after(deps.storage, &mapped.sync_committee_poseidon_hashes, period)?;
+        self.emit_checkpoint(&checkpoint);
+
+        let sequence = match self
+            .state
+            .mapped
+            .rebootstraps
+            .keys(deps.storage, None, None, Order::Descending)
+            .next()
+        {
+            Some(last) => last? + 1,
+            None => 0,
+        };
+        let record = RebootstrapRecord {
+            sender: self.ctx.info.as_ref().map(|info| info.sender.clone()),
+            block_height: self.ctx.env.block.height,
+            block_time: self.ctx.env.block.time.seconds(),
+            previous_head_slot,
+            checkpoint,
+        };
+        self.state
+            .mapped
+            .rebootstraps
+            .save(deps.storage, sequence, &record)?;
+        self.emit(LightClientEvent::Rebootstrapped {
+            sequence,
+            sender: record.sender,
+            previous_head_slot,
+            head_slot: record.checkpoint.head_slot,
+        });
+
+        Ok(())
+    }
+}
+
+fn remove_after<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    map: &Map<u64, T>,
+    key: u64,
+) -> StdResult<()> {
+    let keys = map
+        .keys(storage, Some(Bound::exclusive(key)), None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in keys {
+        map.remove(storage, key);
+    }
+
+    Ok(())
+}
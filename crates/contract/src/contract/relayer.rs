@@ -0,0 +1,57 @@
+use super::Contract;
+use crate::error::ContractError;
+use cosmwasm_std::{Deps, DepsMut, Empty};
+
+impl Contract<'_> {
+    /// Fails if the allowlist is enabled and the sender is not on it
+    pub fn assert_relayer(&self, deps: Deps) -> Result<(), ContractError> {
+        if !self.is_relayer_allowlist_enabled(deps)? {
+            return Ok(());
+        }
+
+        let sender = self.sender()?;
+        if !self.state.mapped.relayers.has(deps.storage, sender) {
+            return Err(ContractError::UnauthorizedRelayer {
+                relayer: sender.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn add_relayer(&self, deps: DepsMut, relayer: String) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let relayer = deps.api.addr_validate(&relayer)?;
+        self.state
+            .mapped
+            .relayers
+            .save(deps.storage, &relayer, &Empty {})?;
+
+        Ok(())
+    }
+
+    pub fn remove_relayer(&self, deps: DepsMut, relayer: String) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let relayer = deps.api.addr_validate(&relayer)?;
+        self.state.mapped.relayers.remove(deps.storage, &relayer);
+
+        Ok(())
+    }
+
+    pub fn set_relayer_allowlist(&self, deps: DepsMut, enabled: bool) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+        self.state.relayer_allowlist.save(deps.storage, &enabled)?;
+
+        Ok(())
+    }
+
+    pub fn is_relayer_allowlist_enabled(&self, deps: Deps) -> Result<bool, ContractError> {
+        Ok(self
+            .state
+            .relayer_allowlist
+            .may_load(deps.storage)?
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,109 @@
+use super::Contract;
+use crate::error::ContractError;
+use cosmwasm_std::{Deps, DepsMut, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Max entries removed from each map per update, spreads the cost of pruning
+/// a large backlog over many transactions
+const MAX_PRUNED_PER_UPDATE: usize = 16;
+
+impl Contract<'_> {
+    pub fn set_retention_window(
+        &self,
+        deps: DepsMut,
+        slots: Option<u64>,
+    ) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        match slots {
+            Some(slots) => self.state.retention_slots.save(deps.storage, &slots)?,
+            None => self.state.retention_slots.remove(deps.storage),
+        }
+
+        Ok(())
+    }
+
+    /// First slot of the retention window of the head, `None` if roots are kept forever
+    pub(crate) fn retained_from(&self, deps: Deps) -> Result<Option<u64>, ContractError> {
+        let Some(retention_slots) = self.state.retention_slots.may_load(deps.storage)? else {
+            return Ok(None);
+        };
+        let head_slot = self.state.non_mapped.load(deps.storage)?.head_slot;
+
+        Ok(Some(head_slot.saturating_sub(retention_slots)))
+    }
+
+    /// Whether roots of `slot` fell out of the retention window of the head
+    pub(crate) fn outside_retention_window(
+        &self,
+        deps: Deps,
+        slot: u64,
+    ) -> Result<bool, ContractError> {
+        Ok(self
+            .retained_from(deps)?
+            .is_some_and(|retained_from| slot < retained_from))
+    }
+
+    /// Loads the entry of `slot` unless it fell out of the retention window.
+    /// Pruning is bounded per update so older entries may linger, they are
+    /// treated as pruned all the same.
+    pub(crate) fn load_retained<T: Serialize + DeserializeOwned>(
+        &self,
+        deps: Deps,
+        map: &Map<u64, T>,
+        slot: u64,
+    ) -> Result<Option<T>, ContractError> {
+        if self.outside_retention_window(deps, slot)? {
+            return Ok(None);
+        }
+
+        Ok(map.may_load(deps.storage, slot)?)
+    }
+
+    /// Removes the oldest roots that fell out of the retention window of `head_slot`
+    pub(crate) fn prune_roots(&self, deps: DepsMut, head_slot: u64) -> Result<(), ContractError> {
+        let Some(retention_slots) = self.state.retention_slots.may_load(deps.storage)? else {
+            return Ok(());
+        };
+        let Some(cutoff_slot) = head_slot.checked_sub(retention_slots) else {
+            return Ok(());
+        };
+
+        prune_before(deps.storage, &self.state.mapped.header_roots, cutoff_slot)?;
+        prune_before(
+            deps.storage,
+            &self.state.mapped.execution_state_roots,
+            cutoff_slot,
+        )?;
+        prune_before(
+            deps.storage,
+            &self.state.mapped.finalized_header_infos,
+            cutoff_slot,
+        )?;
+
+        Ok(())
+    }
+}
+
+fn prune_before<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    roots: &Map<u64, T>,
+    cutoff_slot: u64,
+) -> StdResult<()> {
+    let slots = roots
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(cutoff_slot)),
+            Order::Ascending,
+        )
+        .take(MAX_PRUNED_PER_UPDATE)
+        .collect::<StdResult<Vec<_>>>()?;
+    for slot in slots {
+        roots.remove(storage, slot);
+    }
+
+    Ok(())
+}
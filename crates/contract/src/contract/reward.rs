@@ -0,0 +1,84 @@
+use super::Contract;
+use crate::error::ContractError;
+use cosmwasm_std::{Coin, DepsMut, StdError, Uint128};
+
+impl Contract<'_> {
+    /// Adds the sent funds to the reward pool, returns the new pool balance
+    pub fn fund_rewards(&self, deps: DepsMut) -> Result<Uint128, ContractError> {
+        let reward = self
+            .state
+            .reward
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoRewardConfig {})?;
+
+        let amount = match self.funds() {
+            [coin] if coin.denom == reward.denom && !coin.amount.is_zero() => coin.amount,
+            _ => {
+                return Err(ContractError::InvalidRewardFunds {
+                    denom: reward.denom,
+                })
+            }
+        };
+
+        let pool = self
+            .state
+            .reward_pool
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .checked_add(amount)
+            .map_err(StdError::from)?;
+        self.state.reward_pool.save(deps.storage, &pool)?;
+
+        Ok(pool)
+    }
+
+    /// The reward denom can only change once the pool is drained
+    pub fn set_reward_config(&self, deps: DepsMut, reward: Coin) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        if let Some(current) = self.state.reward.may_load(deps.storage)? {
+            let pool = self
+                .state
+                .reward_pool
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            if current.denom != reward.denom && !pool.is_zero() {
+                return Err(ContractError::RewardDenomLocked {
+                    denom: current.denom,
+                });
+            }
+        }
+        self.state.reward.save(deps.storage, &reward)?;
+
+        Ok(())
+    }
+
+    /// Queues the reward for the sender, skipped when the pool can't cover it
+    pub(crate) fn pay_reward(&self, deps: DepsMut) -> Result<(), ContractError> {
+        let Some(reward) = self.state.reward.may_load(deps.storage)? else {
+            return Ok(());
+        };
+        if reward.amount.is_zero() {
+            return Ok(());
+        }
+
+        let pool = self
+            .state
+            .reward_pool
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        if pool < reward.amount {
+            self.logs
+                .borrow_mut()
+                .push(format!("reward pool exhausted, balance {pool}"));
+            return Ok(());
+        }
+
+        self.state
+            .reward_pool
+            .save(deps.storage, &(pool - reward.amount))?;
+        self.send(self.sender()?, reward);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use super::Contract;
+use crate::error::ContractError;
+use crate::msg::{SubscriberMsg, SubscriptionLimits};
+use cosmwasm_std::{to_binary, Addr, Deps, DepsMut, Order, StdResult, SubMsg, WasmMsg};
+
+impl Contract<'_> {
+    /// Registers the sender for head update callbacks, returns its subscriber id.
+    /// Only contracts can subscribe, so callback slots can't be taken by
+    /// accounts that would fail every callback.
+    pub fn subscribe(&self, deps: DepsMut) -> Result<u64, ContractError> {
+        let sender = self.sender()?;
+        if deps.querier.query_wasm_contract_info(sender).is_err() {
+            return Err(ContractError::SubscriberNotContract {
+                subscriber: sender.clone(),
+            });
+        }
+        let subscribers = self.subscriptions(deps.as_ref())?;
+        if subscribers
+            .iter()
+            .any(|(_, subscriber)| subscriber == sender)
+        {
+            return Err(ContractError::AlreadySubscribed {
+                subscriber: sender.clone(),
+            });
+        }
+
+        let limits = self.subscription_limits(deps.as_ref())?;
+        if subscribers.len() >= limits.max_subscribers as usize {
+            return Err(ContractError::TooManySubscribers {
+                max: limits.max_subscribers,
+            });
+        }
+
+        let id = self
+            .state
+            .next_subscriber_id
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        self.state
+            .mapped
+            .subscribers
+            .save(deps.storage, id, sender)?;
+        self.state
+            .next_subscriber_id
+            .save(deps.storage, &(id + 1))?;
+
+        Ok(id)
+    }
+
+    pub fn unsubscribe(&self, deps: DepsMut) -> Result<(), ContractError> {
+        let sender = self.sender()?.clone();
+        self.remove_subscription(deps, sender)
+    }
+
+    pub fn remove_subscriber(
+        &self,
+        deps: DepsMut,
+        subscriber: String,
+    ) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+
+        let subscriber = deps.api.addr_validate(&subscriber)?;
+        self.remove_subscription(deps, subscriber)
+    }
+
+    /// Lowering `max_subscribers` keeps existing subscribers but rejects new ones
+    pub fn set_subscription_limits(
+        &self,
+        deps: DepsMut,
+        limits: SubscriptionLimits,
+    ) -> Result<(), ContractError> {
+        self.assert_admin(deps.as_ref())?;
+        self.state.subscription_limits.save(deps.storage, &limits)?;
+
+        Ok(())
+    }
+
+    pub fn subscription_limits(&self, deps: Deps) -> Result<SubscriptionLimits, ContractError> {
+        Ok(self
+            .state
+            .subscription_limits
+            .may_load(deps.storage)?
+            .unwrap_or_default())
+    }
+
+    /// Subscriber owning the callbacks sent with reply id `id`
+    pub fn subscriber(&self, deps: Deps, id: u64) -> Result<Option<Addr>, ContractError> {
+        Ok(self.state.mapped.subscribers.may_load(deps.storage, id)?)
+    }
+
+    /// Queues a head update callback for every subscriber. Callbacks reply on
+    /// error only, so a failing subscriber is reverted on its own without
+    /// failing the update.
+    pub(crate) fn notify_subscribers(
+        &self,
+        deps: Deps,
+        slot: u64,
+        header_root: &[u8],
+        execution_state_root: &[u8],
+    ) -> Result<(), ContractError> {
+        let subscribers = self.subscriptions(deps)?;
+        if subscribers.is_empty() {
+            return Ok(());
+        }
+
+        let gas_limit = self.subscription_limits(deps)?.callback_gas_limit;
+        let msg = to_binary(&SubscriberMsg::LightClientHeadUpdated {
+            slot,
+            header_root: header_root.to_vec(),
+            execution_state_root: execution_state_root.to_vec(),
+        })?;
+        for (id, subscriber) in subscribers {
+            let callback = WasmMsg::Execute {
+                contract_addr: subscriber.to_string(),
+                msg: msg.clone(),
+                funds: vec![],
+            };
+            self.messages
+                .borrow_mut()
+                .push(SubMsg::reply_on_error(callback, id).with_gas_limit(gas_limit));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn subscriptions(&self, deps: Deps) -> Result<Vec<(u64, Addr)>, ContractError> {
+        Ok(self
+            .state
+            .mapped
+            .subscribers
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?)
+    }
+
+    fn remove_subscription(&self, deps: DepsMut, subscriber: Addr) -> Result<(), ContractError> {
+        let id = self
+            .subscriptions(deps.as_ref())?
+            .into_iter()
+            .find_map(|(id, addr)| (addr == subscriber).then_some(id))
+            .ok_or(ContractError::NotSubscribed { subscriber })?;
+        self.state.mapped.subscribers.remove(deps.storage, id);
+
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
+use crate::contract::instantiate::CONTRACT_VERSION;
 use crate::contract::Contract;
 use crate::error::ContractError;
 use crate::helpers::TryToBinary;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{
+    Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsgResult,
+};
 
 // TODO optimize after reading eth2 light client spec
 // TODO review cargo xtasks
@@ -28,7 +31,7 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     let mut contract = Contract::new(env, Some(info.clone()));
-    contract.init(deps, msg.init_input.clone());
+    contract.init(deps, msg.init_input.clone())?;
 
     Ok(contract
         .response_with_logs(
@@ -57,13 +60,318 @@ pub fn execute(
             light_client_update,
         } => {
             resp = resp.add_attribute("execute_method", "update_light_client");
-            contract.update_light_client(deps, light_client_update)
+            let outcome = contract.update_light_client(deps, light_client_update)?;
+            resp = resp
+                .add_attribute("head_advanced", outcome.head_advanced.to_string())
+                .add_attribute(
+                    "sync_committee_added",
+                    outcome.sync_committee_added.to_string(),
+                );
+        }
+        ExecuteMsg::UpdateLightClientBatch { updates } => {
+            resp = resp
+                .add_attribute("execute_method", "update_light_client_batch")
+                .add_attribute("updates", updates.len().to_string());
+            let finalized_slots: Vec<u64> = updates.iter().map(|u| u.finalized_slot).collect();
+            let outcomes = contract.update_light_client_batch(deps, updates)?;
+            for (i, (slot, outcome)) in finalized_slots.iter().zip(outcomes).enumerate() {
+                resp = resp
+                    .add_attribute(format!("update_{i}_finalized_slot"), slot.to_string())
+                    .add_attribute(
+                        format!("update_{i}_head_advanced"),
+                        outcome.head_advanced.to_string(),
+                    )
+                    .add_attribute(
+                        format!("update_{i}_sync_committee_added"),
+                        outcome.sync_committee_added.to_string(),
+                    );
+            }
+        }
+        ExecuteMsg::ProposeAdmin { new_admin } => {
+            resp = resp
+                .add_attribute("execute_method", "propose_admin")
+                .add_attribute("new_admin", new_admin.clone());
+            contract.propose_admin(deps, new_admin)?;
+        }
+        ExecuteMsg::AcceptAdmin {} => {
+            resp = resp.add_attribute("execute_method", "accept_admin");
+            contract.accept_admin(deps)?;
+        }
+        ExecuteMsg::RenounceAdmin {} => {
+            resp = resp.add_attribute("execute_method", "renounce_admin");
+            contract.renounce_admin(deps)?;
+        }
+        ExecuteMsg::SubmitOptimisticUpdate {
+            light_client_update,
+        } => {
+            resp = resp
+                .add_attribute("execute_method", "submit_optimistic_update")
+                .add_attribute(
+                    "finalized_slot",
+                    light_client_update.finalized_slot.to_string(),
+                );
+            let challenge_deadline =
+                contract.submit_optimistic_update(deps, light_client_update)?;
+            resp = resp.add_attribute("challenge_deadline", challenge_deadline.to_string());
+        }
+        ExecuteMsg::ChallengeOptimisticUpdate {
+            light_client_update,
+            pending_slot,
+        } => {
+            resp = resp
+                .add_attribute("execute_method", "challenge_optimistic_update")
+                .add_attribute(
+                    "finalized_slot",
+                    pending_slot
+                        .unwrap_or(light_client_update.finalized_slot)
+                        .to_string(),
+                );
+            contract.challenge_optimistic_update(deps, light_client_update, pending_slot)?;
+        }
+        ExecuteMsg::FinalizeOptimisticUpdate { finalized_slot } => {
+            resp = resp
+                .add_attribute("execute_method", "finalize_optimistic_update")
+                .add_attribute("finalized_slot", finalized_slot.to_string());
+            contract.finalize_optimistic_update(deps, finalized_slot)?;
+        }
+        ExecuteMsg::SetOptimisticConfig { config } => {
+            resp = resp.add_attribute("execute_method", "set_optimistic_config");
+            contract.set_optimistic_config(deps, config)?;
+        }
+        ExecuteMsg::SetRetentionWindow { slots } => {
+            resp = resp.add_attribute("execute_method", "set_retention_window");
+            if let Some(slots) = slots {
+                resp = resp.add_attribute("retention_slots", slots.to_string());
+            }
+            contract.set_retention_window(deps, slots)?;
+        }
+        ExecuteMsg::SetParticipationConfig { config } => {
+            resp = resp
+                .add_attribute("execute_method", "set_participation_config")
+                .add_attribute(
+                    "threshold",
+                    format!(
+                        "{}/{}",
+                        config.threshold_numerator, config.threshold_denominator
+                    ),
+                )
+                .add_attribute(
+                    "sync_committee_size",
+                    config.sync_committee_size.to_string(),
+                );
+            contract.set_participation_config(deps, config)?;
+        }
+        ExecuteMsg::FundRewards {} => {
+            resp = resp.add_attribute("execute_method", "fund_rewards");
+            let pool = contract.fund_rewards(deps)?;
+            resp = resp.add_attribute("reward_pool", pool);
+        }
+        ExecuteMsg::SetRewardConfig { reward } => {
+            resp = resp
+                .add_attribute("execute_method", "set_reward_config")
+                .add_attribute("reward", reward.to_string());
+            contract.set_reward_config(deps, reward)?;
+        }
+        ExecuteMsg::Pause {} => {
+            resp = resp.add_attribute("execute_method", "pause");
+            contract.set_paused(deps, true)?;
+        }
+        ExecuteMsg::Unpause {} => {
+            resp = resp.add_attribute("execute_method", "unpause");
+            contract.set_paused(deps, false)?;
+        }
+        ExecuteMsg::AddRelayer { relayer } => {
+            resp = resp
+                .add_attribute("execute_method", "add_relayer")
+                .add_attribute("relayer", relayer.clone());
+            contract.add_relayer(deps, relayer)?;
+        }
+        ExecuteMsg::RemoveRelayer { relayer } => {
+            resp = resp
+                .add_attribute("execute_method", "remove_relayer")
+                .add_attribute("relayer", relayer.clone());
+            contract.remove_relayer(deps, relayer)?;
+        }
+        ExecuteMsg::SetRelayerAllowlist { enabled } => {
+            resp = resp
+                .add_attribute("execute_method", "set_relayer_allowlist")
+                .add_attribute("enabled", enabled.to_string());
+            contract.set_relayer_allowlist(deps, enabled)?;
+        }
+        ExecuteMsg::Subscribe {} => {
+            resp = resp.add_attribute("execute_method", "subscribe");
+            let id = contract.subscribe(deps)?;
+            resp = resp.add_attribute("subscriber_id", id.to_string());
+        }
+        ExecuteMsg::Unsubscribe {} => {
+            resp = resp.add_attribute("execute_method", "unsubscribe");
+            contract.unsubscribe(deps)?;
+        }
+        ExecuteMsg::RemoveSubscriber { subscriber } => {
+            resp = resp
+                .add_attribute("execute_method", "remove_subscriber")
+                .add_attribute("subscriber", subscriber.clone());
+            contract.remove_subscriber(deps, subscriber)?;
+        }
+        ExecuteMsg::SetSubscriptionLimits { limits } => {
+            resp = resp
+                .add_attribute("execute_method", "set_subscription_limits")
+                .add_attribute("max_subscribers", limits.max_subscribers.to_string())
+                .add_attribute("callback_gas_limit", limits.callback_gas_limit.to_string());
+            contract.set_subscription_limits(deps, limits)?;
+        }
+        ExecuteMsg::Rebootstrap { checkpoint } => {
+            resp = resp.add_attribute("execute_method", "rebootstrap");
+            contract.rebootstrap(deps, checkpoint)?;
+        }
+        ExecuteMsg::RegisterVerifyingKey {
+            circuit,
+            vkey_string,
+            activation_slot,
+        } => {
+            resp = resp
+                .add_attribute("execute_method", "register_verifying_key")
+                .add_attribute("activation_slot", activation_slot.to_string());
+            let version =
+                contract.register_verifying_key(deps, circuit, vkey_string, activation_slot)?;
+            resp = resp.add_attribute("version", version.to_string());
         }
     };
 
     Ok(contract.response_with_logs(resp.add_attribute("caller", info.sender)))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    let contract = Contract::new_sudo(env);
+    let mut resp = Response::new().add_attribute("method", "sudo");
+
+    match msg {
+        SudoMsg::Rebootstrap { checkpoint } => {
+            resp = resp.add_attribute("sudo_method", "rebootstrap");
+            contract.rebootstrap(deps, checkpoint)?;
+        }
+        SudoMsg::RegisterVerifyingKey {
+            circuit,
+            vkey_string,
+            activation_slot,
+        } => {
+            resp = resp
+                .add_attribute("sudo_method", "register_verifying_key")
+                .add_attribute("activation_slot", activation_slot.to_string());
+            let version =
+                contract.register_verifying_key(deps, circuit, vkey_string, activation_slot)?;
+            resp = resp.add_attribute("version", version.to_string());
+        }
+        SudoMsg::Pause {} => {
+            resp = resp.add_attribute("sudo_method", "pause");
+            contract.set_paused(deps, true)?;
+        }
+        SudoMsg::Unpause {} => {
+            resp = resp.add_attribute("sudo_method", "unpause");
+            contract.set_paused(deps, false)?;
+        }
+        SudoMsg::AddRelayer { relayer } => {
+            resp = resp
+                .add_attribute("sudo_method", "add_relayer")
+                .add_attribute("relayer", relayer.clone());
+            contract.add_relayer(deps, relayer)?;
+        }
+        SudoMsg::RemoveRelayer { relayer } => {
+            resp = resp
+                .add_attribute("sudo_method", "remove_relayer")
+                .add_attribute("relayer", relayer.clone());
+            contract.remove_relayer(deps, relayer)?;
+        }
+        SudoMsg::SetRelayerAllowlist { enabled } => {
+            resp = resp
+                .add_attribute("sudo_method", "set_relayer_allowlist")
+                .add_attribute("enabled", enabled.to_string());
+            contract.set_relayer_allowlist(deps, enabled)?;
+        }
+        SudoMsg::SetRetentionWindow { slots } => {
+            resp = resp.add_attribute("sudo_method", "set_retention_window");
+            if let Some(slots) = slots {
+                resp = resp.add_attribute("retention_slots", slots.to_string());
+            }
+            contract.set_retention_window(deps, slots)?;
+        }
+        SudoMsg::SetParticipationConfig { config } => {
+            resp = resp
+                .add_attribute("sudo_method", "set_participation_config")
+                .add_attribute(
+                    "threshold",
+                    format!(
+                        "{}/{}",
+                        config.threshold_numerator, config.threshold_denominator
+                    ),
+                )
+                .add_attribute(
+                    "sync_committee_size",
+                    config.sync_committee_size.to_string(),
+                );
+            contract.set_participation_config(deps, config)?;
+        }
+        SudoMsg::SetRewardConfig { reward } => {
+            resp = resp
+                .add_attribute("sudo_method", "set_reward_config")
+                .add_attribute("reward", reward.to_string());
+            contract.set_reward_config(deps, reward)?;
+        }
+        SudoMsg::SetOptimisticConfig { config } => {
+            resp = resp.add_attribute("sudo_method", "set_optimistic_config");
+            contract.set_optimistic_config(deps, config)?;
+        }
+        SudoMsg::RemoveSubscriber { subscriber } => {
+            resp = resp
+                .add_attribute("sudo_method", "remove_subscriber")
+                .add_attribute("subscriber", subscriber.clone());
+            contract.remove_subscriber(deps, subscriber)?;
+        }
+        SudoMsg::SetSubscriptionLimits { limits } => {
+            resp = resp
+                .add_attribute("sudo_method", "set_subscription_limits")
+                .add_attribute("max_subscribers", limits.max_subscribers.to_string())
+                .add_attribute("callback_gas_limit", limits.callback_gas_limit.to_string());
+            contract.set_subscription_limits(deps, limits)?;
+        }
+    };
+
+    Ok(contract.response_with_logs(resp))
+}
+
+/// Handles failed subscriber callbacks, only recorded so the head update they
+/// were sent with still succeeds
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let contract = Contract::new(env, None);
+    let mut resp = Response::new()
+        .add_attribute("method", "reply")
+        .add_attribute("subscriber_id", msg.id.to_string());
+
+    if let SubMsgResult::Err(error) = msg.result {
+        if let Some(subscriber) = contract.subscriber(deps.as_ref(), msg.id)? {
+            resp = resp.add_attribute("subscriber", subscriber);
+        }
+        resp = resp.add_attribute("callback_error", error);
+    }
+
+    Ok(contract.response_with_logs(resp))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract = Contract::new(env, None);
+    let from_version = contract.migrate(deps)?;
+
+    Ok(contract.response_with_logs(
+        Response::new()
+            .add_attribute("method", "migrate")
+            .add_attribute("from_version", from_version)
+            .add_attribute("to_version", CONTRACT_VERSION),
+    ))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let res = try_query(deps, env, msg).map_err(|e| StdError::generic_err(e.to_string()))?;
@@ -74,19 +382,91 @@ pub fn try_query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, Contract
     let contract = Contract::new(env, None);
 
     let res = match msg {
-        QueryMsg::Head {} => contract.head(deps).try_to_binary()?,
-        QueryMsg::HeaderRoot { slot } => contract.header_root(deps, slot).try_to_binary()?,
+        QueryMsg::Head {} => contract.head(deps)?.try_to_binary()?,
+        QueryMsg::Status { max_lag } => contract.status(deps, max_lag)?.try_to_binary()?,
+        QueryMsg::Config {} => contract.config(deps)?.try_to_binary()?,
+        QueryMsg::Admin {} => contract.admin(deps)?.try_to_binary()?,
+        QueryMsg::Relayers {} => contract.relayers(deps)?.try_to_binary()?,
+        QueryMsg::RewardConfig {} => contract.reward_config(deps)?.try_to_binary()?,
+        QueryMsg::RewardBalance {} => contract.reward_balance(deps)?.try_to_binary()?,
+        QueryMsg::OldestRetainedSlot {} => contract.oldest_retained_slot(deps)?.try_to_binary()?,
+        QueryMsg::ParticipationConfig {} => contract.participation(deps)?.try_to_binary()?,
+        QueryMsg::OptimisticConfig {} => contract.optimistic_config(deps)?.try_to_binary()?,
+        QueryMsg::PendingUpdate { finalized_slot } => contract
+            .pending_update(deps, finalized_slot)?
+            .try_to_binary()?,
+        QueryMsg::HeaderRoot { slot } => contract.header_root(deps, slot)?.try_to_binary()?,
+        QueryMsg::FinalizedHeaderInfo { slot } => contract
+            .finalized_header_info(deps, slot)?
+            .try_to_binary()?,
+        QueryMsg::NearestFinalizedSlot {
+            at_or_after,
+            at_or_before,
+        } => contract
+            .nearest_finalized_slot(deps, at_or_after, at_or_before)?
+            .try_to_binary()?,
         QueryMsg::ExecutionStateRoot { slot } => {
-            contract.execution_state_root(deps, slot).try_to_binary()?
+            contract.execution_state_root(deps, slot)?.try_to_binary()?
         }
         QueryMsg::SyncCommitteePoseidonHash { period } => contract
-            .sync_committee_poseidon_hash(deps, period)
+            .sync_committee_poseidon_hash(deps, period)?
+            .try_to_binary()?,
+        QueryMsg::HeaderRoots {
+            start_after,
+            limit,
+            order,
+        } => contract
+            .header_roots(deps, start_after, limit, order)?
+            .try_to_binary()?,
+        QueryMsg::ExecutionStateRoots {
+            start_after,
+            limit,
+            order,
+        } => contract
+            .execution_state_roots(deps, start_after, limit, order)?
+            .try_to_binary()?,
+        QueryMsg::SyncCommitteePoseidonHashes {
+            start_after,
+            limit,
+            order,
+        } => contract
+            .sync_committee_poseidon_hashes(deps, start_after, limit, order)?
             .try_to_binary()?,
         QueryMsg::VerifyLogEntry {
             verify_log_entry_request,
         } => contract
-            .verify_log_entry(deps, verify_log_entry_request)
+            .verify_log_entry(deps, verify_log_entry_request)?
+            .try_to_binary()?,
+        QueryMsg::VerifyTransaction {
+            verify_transaction_request,
+        } => contract
+            .verify_transaction(deps, verify_transaction_request)?
+            .try_to_binary()?,
+        QueryMsg::VerifyExecutionHeader {
+            verify_execution_header_request,
+        } => contract
+            .verify_execution_header(deps, verify_execution_header_request)?
+            .try_to_binary()?,
+        QueryMsg::VerifyWithdrawal {
+            verify_withdrawal_request,
+        } => contract
+            .verify_withdrawal(deps, verify_withdrawal_request)?
             .try_to_binary()?,
+        QueryMsg::VerifyAccount {
+            verify_account_request,
+        } => contract
+            .verify_account(deps, verify_account_request)?
+            .try_to_binary()?,
+        QueryMsg::VerifyStorage {
+            verify_storage_request,
+        } => contract
+            .verify_storage(deps, verify_storage_request)?
+            .try_to_binary()?,
+        QueryMsg::VerifyingKeys { circuit } => {
+            contract.verifying_keys(deps, circuit)?.try_to_binary()?
+        }
+        QueryMsg::RebootstrapHistory {} => contract.rebootstrap_history(deps)?.try_to_binary()?,
+        QueryMsg::Subscribers {} => contract.subscribers(deps)?.try_to_binary()?,
     };
 
     Ok(res)
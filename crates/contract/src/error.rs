@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Addr, Coin, OverflowError, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,10 +6,159 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("Unauthorized")]
     Unauthorized {},
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+
+    #[error("No pending admin to accept")]
+    NoPendingAdmin {},
+
+    #[error("Relayer {relayer} is not allowed to submit updates")]
+    UnauthorizedRelayer { relayer: Addr },
+
+    #[error("Reward config not set")]
+    NoRewardConfig {},
+
+    #[error("Reward funds must be sent in {denom} only")]
+    InvalidRewardFunds { denom: String },
+
+    #[error("Reward pool still holds {denom}")]
+    RewardDenomLocked { denom: String },
+
+    #[error("Optimistic updates are disabled")]
+    OptimisticModeDisabled {},
+
+    #[error("Optimistic updates must be bonded with exactly {bond}")]
+    InvalidBond { bond: Coin },
+
+    #[error("Pending update already posted for slot {slot}")]
+    PendingUpdateExists { slot: u64 },
+
+    #[error("No pending update for slot {slot}")]
+    NoPendingUpdate { slot: u64 },
+
+    #[error("Challenge period of pending update ends at {deadline}")]
+    ChallengePeriodActive { deadline: u64 },
+
+    #[error("Invalid optimistic config: {reason}")]
+    InvalidOptimisticConfig { reason: String },
+
+    #[error("Challenge update does not disprove the pending update for slot {slot}")]
+    ChallengeDoesNotDisprove { slot: u64 },
+
+    #[error("Subscriber limit of {max} reached")]
+    TooManySubscribers { max: u32 },
+
+    #[error("{subscriber} is already subscribed")]
+    AlreadySubscribed { subscriber: Addr },
+
+    #[error("{subscriber} is not subscribed")]
+    NotSubscribed { subscriber: Addr },
+
+    #[error("{subscriber} is not a contract and cannot subscribe")]
+    SubscriberNotContract { subscriber: Addr },
+
+    #[error("Light client updates are paused")]
+    Paused {},
+
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    InvalidMigrationContract { expected: String, found: String },
+
+    #[error("Cannot migrate from version {from} to older version {to}")]
+    MigrationDowngrade { from: String, to: String },
+
+    #[error("Invalid contract version {version}")]
+    InvalidContractVersion { version: String },
+
+    #[error("Unknown network {network}")]
+    UnknownNetwork { network: String },
+
+    #[error("Invalid verifying key: {reason}")]
+    InvalidVerifyingKey { reason: String },
+
+    #[error("Finalized slot is behind head slot, finalized slot {finalized_slot}, head slot {head_slot}")]
+    FinalizedSlotBehindHead { finalized_slot: u64, head_slot: u64 },
+
+    #[error("Participation is less than {threshold_numerator}/{threshold_denominator} threshold, participation {participation}")]
+    InsufficientParticipation {
+        participation: u64,
+        threshold_numerator: u64,
+        threshold_denominator: u64,
+    },
+
+    #[error("Invalid participation config: {reason}")]
+    InvalidParticipationConfig { reason: String },
+
+    #[error("Sync committee hash not known for period {period}")]
+    UnknownSyncCommittee { period: u64 },
+
+    #[error("Update {index} of batch failed: {source}")]
+    BatchUpdate {
+        index: u64,
+        source: Box<ContractError>,
+    },
+
+    #[error("Malformed light client update: {reason}")]
+    MalformedUpdate { reason: String },
+
+    #[error("Next sync committee missing from light client update")]
+    MissingNextSyncCommittee {},
+
+    #[error("No verifying key active at slot {slot}")]
+    NoVerifyingKey { slot: u64 },
+
+    #[error("Activation slot {activation_slot} must be after {min_slot}")]
+    InvalidActivationSlot { activation_slot: u64, min_slot: u64 },
+
+    #[error("Failed to verify lc_update proof")]
+    InvalidLcUpdateProof {},
+
+    #[error("Failed to verify sc_update proof")]
+    InvalidScUpdateProof {},
+
+    #[error("Failed to check proof: {reason}")]
+    ProofVerification { reason: String },
+
+    #[error("Header root already set with different value at slot {slot}")]
+    ConflictingHeaderRoot { slot: u64 },
+
+    #[error("Execution state root already set with different value at slot {slot}")]
+    ConflictingExecutionStateRoot { slot: u64 },
+
+    #[error("Sync Committee poseidon hash already set with different value for period {period}")]
+    ConflictingSyncCommitteeHash { period: u64 },
+
+    #[error("Malformed RLP: {0}")]
+    MalformedRlp(#[from] rlp::DecoderError),
+
+    #[error("Invalid hash: {0}")]
+    InvalidHashLength(#[from] types::InvalidLengthError),
+
+    #[error("Header root not known for slot {slot}")]
+    UnknownHeaderRoot { slot: u64 },
+
+    #[error("No finalized header root at or after slot {tx_slot}")]
+    NoFinalizedSlot { tx_slot: u64 },
+
+    #[error("Beacon branch does not match header root at slot {slot}")]
+    InvalidBeaconBranch { slot: u64 },
+
+    #[error("Execution state root not known for slot {slot}")]
+    UnknownExecutionStateRoot { slot: u64 },
+
+    #[error("Malformed proof request: {reason}")]
+    MalformedProofRequest { reason: String },
+
+    #[error("Invalid trie proof: {reason}")]
+    InvalidTrieProof { reason: String },
+
+    #[error("Exactly one of at_or_after and at_or_before must be set")]
+    InvalidSlotBound {},
+
+    #[error("Invalid slot range, src slot {src_slot}, tx slot {tx_slot}")]
+    InvalidSlotRange { src_slot: u64, tx_slot: u64 },
 }
 
 impl From<std::io::Error> for ContractError {
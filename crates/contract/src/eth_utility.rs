@@ -1,10 +1,12 @@
 use cosmwasm_schema::cw_serde;
+use std::fmt;
 use std::str::FromStr;
 use types::eth2::{Epoch, ForkVersion, Slot};
 
 pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
 pub const MIN_SYNC_COMMITTEE_PARTICIPANTS: u64 = 1;
 pub const SLOTS_PER_EPOCH: u64 = 32;
+pub const SECONDS_PER_SLOT: u64 = 12;
 
 #[cw_serde]
 pub enum Network {
@@ -23,18 +25,33 @@ impl FromStr for Network {
     }
 }
 
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Network::Mainnet => "mainnet",
+            Network::Goerli => "goerli",
+        })
+    }
+}
+
 pub struct NetworkConfig {
+    /// Unix timestamp of slot 0
+    pub genesis_time: u64,
     pub genesis_validators_root: [u8; 32],
     pub bellatrix_fork_version: ForkVersion,
     pub bellatrix_fork_epoch: u64,
     pub capella_fork_version: ForkVersion,
     pub capella_fork_epoch: u64,
+    pub deneb_fork_epoch: u64,
+    /// `None` on networks retired before Electra
+    pub electra_fork_epoch: Option<u64>,
 }
 
 impl NetworkConfig {
     pub fn new(network: &Network) -> Self {
         match network {
             Network::Mainnet => Self {
+                genesis_time: 1606824023,
                 genesis_validators_root: [
                     0x4b, 0x36, 0x3d, 0xb9, 0x4e, 0x28, 0x61, 0x20, 0xd7, 0x6e, 0xb9, 0x05, 0x34,
                     0x0f, 0xdd, 0x4e, 0x54, 0xbf, 0xe9, 0xf0, 0x6b, 0xf3, 0x3f, 0xf6, 0xcf, 0x5a,
@@ -44,8 +61,11 @@ impl NetworkConfig {
                 bellatrix_fork_epoch: 144896,
                 capella_fork_version: [0x03, 0x00, 0x00, 0x00],
                 capella_fork_epoch: 194048,
+                deneb_fork_epoch: 269568,
+                electra_fork_epoch: Some(364032),
             },
             Network::Goerli => Self {
+                genesis_time: 1616508000,
                 genesis_validators_root: [
                     0x04, 0x3d, 0xb0, 0xd9, 0xa8, 0x38, 0x13, 0x55, 0x1e, 0xe2, 0xf3, 0x34, 0x50,
                     0xd2, 0x37, 0x97, 0x75, 0x7d, 0x43, 0x09, 0x11, 0xa9, 0x32, 0x05, 0x30, 0xad,
@@ -55,6 +75,8 @@ impl NetworkConfig {
                 bellatrix_fork_epoch: 112260,
                 capella_fork_version: [0x03, 0x00, 0x10, 0x20],
                 capella_fork_epoch: 162304,
+                deneb_fork_epoch: 231680,
+                electra_fork_epoch: None,
             },
         }
     }
@@ -67,9 +89,33 @@ impl NetworkConfig {
         None
     }
 
+    /// Unix timestamp of the start of `slot`
+    pub fn compute_timestamp_at_slot(&self, slot: Slot) -> u64 {
+        self.genesis_time
+            .saturating_add(slot.saturating_mul(SECONDS_PER_SLOT))
+    }
+
     pub fn compute_fork_version_by_slot(&self, slot: Slot) -> Option<ForkVersion> {
         self.compute_fork_version(compute_epoch_at_slot(slot))
     }
+
+    /// Depth of the beacon state field tree at `slot`, Electra grew the state past 32 fields
+    pub fn beacon_state_depth(&self, slot: Slot) -> u32 {
+        match self.electra_fork_epoch {
+            Some(epoch) if compute_epoch_at_slot(slot) >= epoch => 6,
+            _ => 5,
+        }
+    }
+
+    /// Depth of the execution payload header field tree at `slot`, Deneb grew
+    /// the header past 16 fields with the blob gas fields
+    pub fn execution_payload_header_depth(&self, slot: Slot) -> u32 {
+        if compute_epoch_at_slot(slot) >= self.deneb_fork_epoch {
+            5
+        } else {
+            4
+        }
+    }
 }
 
 pub const fn compute_epoch_at_slot(slot: Slot) -> u64 {
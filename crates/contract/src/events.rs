@@ -0,0 +1,153 @@
+use cosmwasm_std::{Addr, Event, StdError, StdResult};
+
+pub const HEAD_UPDATED: &str = "eth2_lc_head_updated";
+pub const SYNC_COMMITTEE_ADDED: &str = "eth2_lc_sync_committee_added";
+pub const ROOT_RECORDED: &str = "eth2_lc_root_recorded";
+pub const REBOOTSTRAPPED: &str = "eth2_lc_rebootstrapped";
+
+/// Prefix the chain adds to the type of events emitted by contracts
+const WASM_EVENT_PREFIX: &str = "wasm-";
+
+/// Typed events emitted on light client state transitions, roots and hashes are hex encoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LightClientEvent {
+    /// Head moved to `slot`
+    HeadUpdated {
+        slot: u64,
+        period: u64,
+        header_root: Vec<u8>,
+        execution_state_root: Vec<u8>,
+    },
+    /// Sync committee poseidon hash stored for `period`
+    SyncCommitteeAdded {
+        period: u64,
+        sync_committee_poseidon_hash: Vec<u8>,
+    },
+    /// Finalized roots stored for `slot`
+    RootRecorded {
+        slot: u64,
+        header_root: Vec<u8>,
+        execution_state_root: Vec<u8>,
+    },
+    /// Re-seeded from a trusted checkpoint at `head_slot`, `sender` is `None` under sudo
+    Rebootstrapped {
+        sequence: u64,
+        sender: Option<Addr>,
+        previous_head_slot: u64,
+        head_slot: u64,
+    },
+}
+
+impl From<LightClientEvent> for Event {
+    fn from(event: LightClientEvent) -> Self {
+        match event {
+            LightClientEvent::HeadUpdated {
+                slot,
+                period,
+                header_root,
+                execution_state_root,
+            } => Event::new(HEAD_UPDATED)
+                .add_attribute("slot", slot.to_string())
+                .add_attribute("period", period.to_string())
+                .add_attribute("header_root", hex::encode(header_root))
+                .add_attribute("execution_state_root", hex::encode(execution_state_root)),
+            LightClientEvent::SyncCommitteeAdded {
+                period,
+                sync_committee_poseidon_hash,
+            } => Event::new(SYNC_COMMITTEE_ADDED)
+                .add_attribute("period", period.to_string())
+                .add_attribute(
+                    "sync_committee_poseidon_hash",
+                    hex::encode(sync_committee_poseidon_hash),
+                ),
+            LightClientEvent::RootRecorded {
+                slot,
+                header_root,
+                execution_state_root,
+            } => Event::new(ROOT_RECORDED)
+                .add_attribute("slot", slot.to_string())
+                .add_attribute("header_root", hex::encode(header_root))
+                .add_attribute("execution_state_root", hex::encode(execution_state_root)),
+            LightClientEvent::Rebootstrapped {
+                sequence,
+                sender,
+                previous_head_slot,
+                head_slot,
+            } => {
+                let event = Event::new(REBOOTSTRAPPED)
+                    .add_attribute("sequence", sequence.to_string())
+                    .add_attribute("previous_head_slot", previous_head_slot.to_string())
+                    .add_attribute("head_slot", head_slot.to_string());
+                match sender {
+                    Some(sender) => event.add_attribute("sender", sender),
+                    None => event,
+                }
+            }
+        }
+    }
+}
+
+impl LightClientEvent {
+    /// Decodes an event as emitted by the contract or as seen on chain, with the
+    /// `wasm-` prefix. Returns `None` for events of other types.
+    pub fn decode(event: &Event) -> StdResult<Option<Self>> {
+        let ty = event
+            .ty
+            .strip_prefix(WASM_EVENT_PREFIX)
+            .unwrap_or(&event.ty);
+
+        let decoded = match ty {
+            HEAD_UPDATED => Self::HeadUpdated {
+                slot: attr_u64(event, "slot")?,
+                period: attr_u64(event, "period")?,
+                header_root: attr_hex(event, "header_root")?,
+                execution_state_root: attr_hex(event, "execution_state_root")?,
+            },
+            SYNC_COMMITTEE_ADDED => Self::SyncCommitteeAdded {
+                period: attr_u64(event, "period")?,
+                sync_committee_poseidon_hash: attr_hex(event, "sync_committee_poseidon_hash")?,
+            },
+            ROOT_RECORDED => Self::RootRecorded {
+                slot: attr_u64(event, "slot")?,
+                header_root: attr_hex(event, "header_root")?,
+                execution_state_root: attr_hex(event, "execution_state_root")?,
+            },
+            REBOOTSTRAPPED => Self::Rebootstrapped {
+                sequence: attr_u64(event, "sequence")?,
+                sender: attr(event, "sender").ok().map(Addr::unchecked),
+                previous_head_slot: attr_u64(event, "previous_head_slot")?,
+                head_slot: attr_u64(event, "head_slot")?,
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(decoded))
+    }
+
+    /// Decodes the light client events among `events`, in order
+    pub fn decode_all(events: &[Event]) -> StdResult<Vec<Self>> {
+        events
+            .iter()
+            .filter_map(|event| Self::decode(event).transpose())
+            .collect()
+    }
+}
+
+fn attr<'a>(event: &'a Event, key: &str) -> StdResult<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+        .ok_or_else(|| StdError::generic_err(format!("{} event is missing {key}", event.ty)))
+}
+
+fn attr_u64(event: &Event, key: &str) -> StdResult<u64> {
+    attr(event, key)?
+        .parse()
+        .map_err(|e| StdError::parse_err("u64", e))
+}
+
+fn attr_hex(event: &Event, key: &str) -> StdResult<Vec<u8>> {
+    hex::decode(attr(event, key)?).map_err(|e| StdError::parse_err("hex", e))
+}
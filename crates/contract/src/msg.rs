@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, Uint256};
 
 #[cw_serde]
 pub struct NextSyncCommittee {
@@ -29,6 +29,130 @@ pub struct InitInput {
     pub sync_committee_poseidon_hash: Vec<u8>,
     pub vkey_lc_update_string: String,
     pub vkey_sc_update_string: String,
+    /// Enables the relayer allowlist with these relayers, updates are permissionless if unset
+    pub relayers: Option<Vec<Addr>>,
+    /// Number of slots behind the head to keep roots for, roots are kept forever if unset
+    pub retention_slots: Option<u64>,
+    /// Mainnet sync committee size and 2/3 threshold if unset
+    pub participation: Option<ParticipationConfig>,
+}
+
+/// Share of the sync committee that must sign an update
+#[cw_serde]
+pub struct ParticipationConfig {
+    pub threshold_numerator: u64,
+    pub threshold_denominator: u64,
+    pub sync_committee_size: u64,
+}
+
+impl Default for ParticipationConfig {
+    fn default() -> Self {
+        Self {
+            threshold_numerator: 2,
+            threshold_denominator: 3,
+            sync_committee_size: 512,
+        }
+    }
+}
+
+/// Trusted state the light client restarts from, same fields as [`InitInput`]
+#[cw_serde]
+pub struct Checkpoint {
+    pub head_slot: u64,
+    pub header_root: Vec<u8>,
+    pub execution_state_root: Vec<u8>,
+    pub sync_committee_poseidon_hash: Vec<u8>,
+}
+
+/// Provenance of a stored finalized header root
+#[cw_serde]
+pub struct FinalizedHeaderInfo {
+    /// Participation of the proven update attesting the root, `None` for
+    /// checkpoints and optimistic updates
+    pub participation: Option<u64>,
+    /// `None` for checkpoints
+    pub attested_slot: Option<u64>,
+    /// `None` when set by governance through sudo
+    pub submitter: Option<Addr>,
+    pub block_height: u64,
+    /// Block time in seconds
+    pub block_time: u64,
+}
+
+/// Audit trail entry of a re-bootstrap
+#[cw_serde]
+pub struct RebootstrapRecord {
+    /// `None` when executed by governance through sudo
+    pub sender: Option<Addr>,
+    pub block_height: u64,
+    /// Block time in seconds
+    pub block_time: u64,
+    pub previous_head_slot: u64,
+    pub checkpoint: Checkpoint,
+}
+
+/// zk circuits whose proofs are verified by the contract
+#[cw_serde]
+pub enum Circuit {
+    LcUpdate,
+    ScUpdate,
+}
+
+#[cw_serde]
+pub struct OptimisticConfig {
+    /// Bond posted with every optimistic update
+    pub bond: Coin,
+    /// Seconds an optimistic update can be challenged for
+    pub challenge_period: u64,
+}
+
+/// Optimistic update waiting for its challenge period to end
+#[cw_serde]
+pub struct PendingUpdate {
+    pub relayer: Addr,
+    pub bond: Coin,
+    pub attested_slot: u64,
+    pub finalized_slot: u64,
+    pub finalized_header_root: Vec<u8>,
+    pub execution_state_root: Vec<u8>,
+    /// Block time in seconds after which the update can be finalized
+    pub challenge_deadline: u64,
+}
+
+#[cw_serde]
+pub struct SubscriptionLimits {
+    /// Max number of registered subscribers
+    pub max_subscribers: u32,
+    /// Gas limit of each head update callback
+    pub callback_gas_limit: u64,
+}
+
+impl Default for SubscriptionLimits {
+    fn default() -> Self {
+        Self {
+            max_subscribers: 16,
+            callback_gas_limit: 200_000,
+        }
+    }
+}
+
+/// Callback executed on subscribed contracts, they must accept it as an
+/// execute message
+#[cw_serde]
+pub enum SubscriberMsg {
+    /// Light client head advanced to `slot`
+    LightClientHeadUpdated {
+        slot: u64,
+        header_root: Vec<u8>,
+        execution_state_root: Vec<u8>,
+    },
+}
+
+/// Iteration order of range queries
+#[cw_serde]
+pub enum RangeOrder {
+    Ascending,
+    Descending,
 }
 
 #[cw_serde]
@@ -36,11 +160,136 @@ pub struct InstantiateMsg {
     pub init_input: InitInput,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     UpdateLightClient {
         light_client_update: LightClientUpdate,
     },
+    /// Apply updates in order, fails as a whole if any update fails
+    UpdateLightClientBatch {
+        updates: Vec<LightClientUpdate>,
+    },
+    /// Propose a new admin, takes effect once the new admin accepts
+    ProposeAdmin {
+        new_admin: String,
+    },
+    AcceptAdmin {},
+    RenounceAdmin {},
+    /// Reject light client updates until unpaused
+    Pause {},
+    Unpause {},
+    AddRelayer {
+        relayer: String,
+    },
+    RemoveRelayer {
+        relayer: String,
+    },
+    /// Restrict light client updates to allowlisted relayers when enabled
+    SetRelayerAllowlist {
+        enabled: bool,
+    },
+    /// Post an update without proofs, bonded and challengeable until the challenge period ends
+    SubmitOptimisticUpdate {
+        light_client_update: LightClientUpdate,
+    },
+    /// Revert the pending update for `pending_slot` with a proven update that
+    /// disproves it, the challenger receives the bond. The pending update is
+    /// disproven by other roots for the same finalized slot, or by a lower
+    /// finalized slot at the same or a later attested slot.
+    ChallengeOptimisticUpdate {
+        light_client_update: LightClientUpdate,
+        /// Finalized slot of the pending update, the proven update's if unset
+        pending_slot: Option<u64>,
+    },
+    /// Apply a pending update whose challenge period ended and release its bond
+    FinalizeOptimisticUpdate {
+        finalized_slot: u64,
+    },
+    /// Enable optimistic updates, `None` disables them
+    SetOptimisticConfig {
+        config: Option<OptimisticConfig>,
+    },
+    /// Change how many slots behind the head roots are kept, `None` disables pruning
+    SetRetentionWindow {
+        slots: Option<u64>,
+    },
+    /// Change the participation threshold, e.g. for devnets with a smaller sync committee
+    SetParticipationConfig {
+        config: ParticipationConfig,
+    },
+    /// Top up the relayer reward pool, funds must be sent in the reward denom
+    FundRewards {},
+    /// Reward paid to the sender of every update that advances the light client
+    SetRewardConfig {
+        reward: Coin,
+    },
+    /// Receive a [`SubscriberMsg`] callback whenever the head advances, only
+    /// contracts can subscribe
+    Subscribe {},
+    Unsubscribe {},
+    RemoveSubscriber {
+        subscriber: String,
+    },
+    SetSubscriptionLimits {
+        limits: SubscriptionLimits,
+    },
+    /// Restart the light client from a trusted checkpoint, e.g. after the
+    /// chain of sync committees broke
+    Rebootstrap {
+        checkpoint: Checkpoint,
+    },
+    /// Register a new verifying key version for `circuit`, used for updates
+    /// with an attested slot at or after `activation_slot`
+    RegisterVerifyingKey {
+        circuit: Circuit,
+        vkey_string: String,
+        activation_slot: u64,
+    },
+}
+
+/// Messages executed by chain governance, which doesn't need the admin
+#[cw_serde]
+pub enum SudoMsg {
+    Rebootstrap {
+        checkpoint: Checkpoint,
+    },
+    RegisterVerifyingKey {
+        circuit: Circuit,
+        vkey_string: String,
+        activation_slot: u64,
+    },
+    Pause {},
+    Unpause {},
+    AddRelayer {
+        relayer: String,
+    },
+    RemoveRelayer {
+        relayer: String,
+    },
+    SetRelayerAllowlist {
+        enabled: bool,
+    },
+    SetRetentionWindow {
+        slots: Option<u64>,
+    },
+    SetParticipationConfig {
+        config: ParticipationConfig,
+    },
+    SetRewardConfig {
+        reward: Coin,
+    },
+    SetOptimisticConfig {
+        config: Option<OptimisticConfig>,
+    },
+    RemoveSubscriber {
+        subscriber: String,
+    },
+    SetSubscriptionLimits {
+        limits: SubscriptionLimits,
+    },
 }
 
 #[cw_serde]
@@ -48,16 +297,90 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(HeadResponse)]
     Head {},
+    /// Head freshness, stale if the head is more than `max_lag` seconds old
+    #[returns(StatusResponse)]
+    Status { max_lag: Option<u64> },
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(AdminResponse)]
+    Admin {},
+    #[returns(RelayersResponse)]
+    Relayers {},
+    #[returns(RewardConfigResponse)]
+    RewardConfig {},
+    #[returns(RewardBalanceResponse)]
+    RewardBalance {},
+    #[returns(OldestRetainedSlotResponse)]
+    OldestRetainedSlot {},
+    #[returns(ParticipationConfigResponse)]
+    ParticipationConfig {},
+    #[returns(OptimisticConfigResponse)]
+    OptimisticConfig {},
+    #[returns(PendingUpdateResponse)]
+    PendingUpdate { finalized_slot: u64 },
     #[returns(HeaderRootResponse)]
     HeaderRoot { slot: u64 },
+    #[returns(FinalizedHeaderInfoResponse)]
+    FinalizedHeaderInfo { slot: u64 },
+    /// Closest slot with a stored header root, exactly one bound must be set
+    #[returns(NearestFinalizedSlotResponse)]
+    NearestFinalizedSlot {
+        at_or_after: Option<u64>,
+        at_or_before: Option<u64>,
+    },
     #[returns(ExecutionStateRootResponse)]
     ExecutionStateRoot { slot: u64 },
     #[returns(SyncCommitteePoseidonHashResponse)]
     SyncCommitteePoseidonHash { period: u64 },
+    /// Stored header roots after `start_after` in `order`, ascending by default
+    #[returns(HeaderRootsResponse)]
+    HeaderRoots {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
+    #[returns(ExecutionStateRootsResponse)]
+    ExecutionStateRoots {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
+    #[returns(SyncCommitteePoseidonHashesResponse)]
+    SyncCommitteePoseidonHashes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
     #[returns(VerifyLogEntryResponse)]
     VerifyLogEntry {
         verify_log_entry_request: VerifyLogEntryRequest,
     },
+    #[returns(VerifyTransactionResponse)]
+    VerifyTransaction {
+        verify_transaction_request: VerifyTransactionRequest,
+    },
+    #[returns(VerifyExecutionHeaderResponse)]
+    VerifyExecutionHeader {
+        verify_execution_header_request: VerifyExecutionHeaderRequest,
+    },
+    #[returns(VerifyWithdrawalResponse)]
+    VerifyWithdrawal {
+        verify_withdrawal_request: VerifyWithdrawalRequest,
+    },
+    #[returns(VerifyAccountResponse)]
+    VerifyAccount {
+        verify_account_request: VerifyAccountRequest,
+    },
+    #[returns(VerifyStorageResponse)]
+    VerifyStorage {
+        verify_storage_request: VerifyStorageRequest,
+    },
+    #[returns(VerifyingKeysResponse)]
+    VerifyingKeys { circuit: Circuit },
+    #[returns(RebootstrapHistoryResponse)]
+    RebootstrapHistory {},
+    #[returns(SubscribersResponse)]
+    Subscribers {},
 }
 #[cw_serde]
 pub struct VerifyLogEntryRequest {
@@ -66,21 +389,205 @@ pub struct VerifyLogEntryRequest {
     pub receipt_index: u64,
     pub receipt_data: Vec<u8>,
     pub proof: Vec<Vec<u8>>,
-    pub src_slot: u64,
+    /// Slot whose header root the receipts root is proven against, the
+    /// nearest stored slot at or after `tx_slot` if unset
+    pub src_slot: Option<u64>,
     pub tx_slot: u64,
     pub receipts_root: Vec<u8>,
     pub receipts_root_proof: Vec<Vec<u8>>,
     pub skip_bridge_call: bool,
+    /// Min proven participation of the update attesting the `src_slot` root
+    pub min_participation: Option<u64>,
+    /// Min seconds since the `src_slot` root was stored
+    pub min_age: Option<u64>,
+}
+
+/// Transaction proof against the transactions root of an execution block
+/// header, whose hash is proven against a stored header root
+#[cw_serde]
+pub struct VerifyTransactionRequest {
+    /// Slot of the beacon block carrying the execution block
+    pub tx_slot: u64,
+    /// Slot whose header root the block hash is proven against, the
+    /// nearest stored slot at or after `tx_slot` if unset
+    pub src_slot: Option<u64>,
+    /// rlp encoded execution block header
+    pub execution_header: Vec<u8>,
+    /// SSZ branch from the `src_slot` header root to the payload block hash
+    pub block_hash_proof: Vec<Vec<u8>>,
+    pub transaction_index: u64,
+    /// Raw transaction, EIP-2718 typed or legacy rlp
+    pub transaction_data: Vec<u8>,
+    /// rlp encoded transactions trie nodes from the root to the transaction
+    pub proof: Vec<Vec<u8>>,
+}
+
+/// Execution block header whose hash is proven against a stored header root
+#[cw_serde]
+pub struct VerifyExecutionHeaderRequest {
+    /// Slot of the beacon block carrying the execution block
+    pub tx_slot: u64,
+    /// Slot whose header root the block hash is proven against, the
+    /// nearest stored slot at or after `tx_slot` if unset
+    pub src_slot: Option<u64>,
+    /// rlp encoded execution block header
+    pub execution_header: Vec<u8>,
+    /// SSZ branch from the `src_slot` header root to the payload block hash
+    pub block_hash_proof: Vec<Vec<u8>>,
+}
+
+/// Withdrawal proof against the execution payload withdrawals, proven
+/// against a stored header root
+#[cw_serde]
+pub struct VerifyWithdrawalRequest {
+    /// Slot of the beacon block carrying the withdrawal
+    pub tx_slot: u64,
+    /// Slot whose header root the withdrawal is proven against, the
+    /// nearest stored slot at or after `tx_slot` if unset
+    pub src_slot: Option<u64>,
+    pub withdrawal: Withdrawal,
+    /// Position of the withdrawal in the payload withdrawals
+    pub position: u64,
+    /// SSZ branch from the `src_slot` header root to the withdrawal
+    pub proof: Vec<Vec<u8>>,
+}
+
+#[cw_serde]
+pub struct Withdrawal {
+    pub index: u64,
+    pub validator_index: u64,
+    /// 20 byte execution address
+    pub address: Vec<u8>,
+    /// Amount in gwei
+    pub amount: u64,
+}
+
+/// `eth_getProof` account proof against the execution state root stored for `slot`
+#[cw_serde]
+pub struct VerifyAccountRequest {
+    pub slot: u64,
+    /// 20 byte account address
+    pub address: Vec<u8>,
+    /// rlp encoded state trie nodes from the root to the account
+    pub account_proof: Vec<Vec<u8>>,
+}
+
+/// `eth_getProof` account and storage proofs against the execution state root stored for `slot`
+#[cw_serde]
+pub struct VerifyStorageRequest {
+    pub slot: u64,
+    /// 20 byte account address
+    pub address: Vec<u8>,
+    /// rlp encoded state trie nodes from the root to the account
+    pub account_proof: Vec<Vec<u8>>,
+    pub storage_proofs: Vec<StorageProof>,
+}
+
+#[cw_serde]
+pub struct StorageProof {
+    /// 32 byte storage slot
+    pub key: Vec<u8>,
+    /// rlp encoded storage trie nodes from the account storage root to the slot
+    pub proof: Vec<Vec<u8>>,
+}
+
+#[cw_serde]
+pub struct RelayersResponse {
+    pub allowlist_enabled: bool,
+    pub relayers: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RewardConfigResponse {
+    pub reward: Option<Coin>,
+}
+
+#[cw_serde]
+pub struct RewardBalanceResponse {
+    /// Unpaid rewards in the reward denom, `None` if no reward is configured
+    pub balance: Option<Coin>,
+}
+
+#[cw_serde]
+pub struct OldestRetainedSlotResponse {
+    /// Oldest slot with a header root in the retention window, proofs against
+    /// older slots can't be verified even if pruning hasn't removed their roots yet
+    pub slot: Option<u64>,
+    pub retention_slots: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ParticipationConfigResponse {
+    pub config: ParticipationConfig,
+}
+
+#[cw_serde]
+pub struct OptimisticConfigResponse {
+    pub config: Option<OptimisticConfig>,
+}
+
+#[cw_serde]
+pub struct PendingUpdateResponse {
+    pub pending_update: Option<PendingUpdate>,
 }
 
 #[cw_serde]
 pub struct HeadResponse {
     pub head: u64,
+    /// Updates are paused, the head may no longer follow the chain
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct StatusResponse {
+    pub head_slot: u64,
+    /// Unix timestamp of the head slot
+    pub head_timestamp: u64,
+    /// Seconds between the head slot and the current block time
+    pub lag: u64,
+    /// Sync committee period of the head
+    pub current_period: u64,
+    /// Updates can follow the chain into the next period
+    pub next_sync_committee_known: bool,
+    /// `None` without `max_lag`
+    pub is_stale: Option<bool>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub admin: Option<Addr>,
+    pub network: String,
+    pub genesis_time: u64,
+    pub genesis_validators_root: Vec<u8>,
+    pub bellatrix_fork_version: Vec<u8>,
+    pub bellatrix_fork_epoch: u64,
+    pub capella_fork_version: Vec<u8>,
+    pub capella_fork_epoch: u64,
+    pub participation: ParticipationConfig,
+    /// lc_update verifying key active at the head slot
+    pub lc_update_vkey: VerifyingKeyInfo,
+    /// sc_update verifying key active at the head slot
+    pub sc_update_vkey: VerifyingKeyInfo,
+}
+
+#[cw_serde]
+pub struct AdminResponse {
+    pub admin: Option<Addr>,
+    pub pending_admin: Option<Addr>,
 }
 
 #[cw_serde]
 pub struct HeaderRootResponse {
+    /// Finalized header root, proven or past its challenge period
     pub header_root: Option<Vec<u8>>,
+    /// Header root of an optimistic update that can still be challenged
+    pub pending_header_root: Option<Vec<u8>>,
+}
+
+#[cw_serde]
+pub struct FinalizedHeaderInfoResponse {
+    /// `None` if no root is stored for the slot or it predates provenance tracking
+    pub info: Option<FinalizedHeaderInfo>,
 }
 
 #[cw_serde]
@@ -88,12 +595,183 @@ pub struct ExecutionStateRootResponse {
     pub execution_state_root: Option<Vec<u8>>,
 }
 
+#[cw_serde]
+pub struct RebootstrapHistoryResponse {
+    /// Oldest first
+    pub records: Vec<RebootstrapRecord>,
+}
+
+#[cw_serde]
+pub struct SubscribersResponse {
+    /// In order of subscription
+    pub subscribers: Vec<Addr>,
+    pub limits: SubscriptionLimits,
+}
+
 #[cw_serde]
 pub struct SyncCommitteePoseidonHashResponse {
     pub sync_committee_poseidon_hash: Option<Vec<u8>>,
 }
 
+#[cw_serde]
+pub struct HeaderRootEntry {
+    pub slot: u64,
+    pub header_root: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct HeaderRootsResponse {
+    pub header_roots: Vec<HeaderRootEntry>,
+}
+
+#[cw_serde]
+pub struct ExecutionStateRootEntry {
+    pub slot: u64,
+    pub execution_state_root: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct ExecutionStateRootsResponse {
+    pub execution_state_roots: Vec<ExecutionStateRootEntry>,
+}
+
+#[cw_serde]
+pub struct SyncCommitteePoseidonHashEntry {
+    pub period: u64,
+    pub sync_committee_poseidon_hash: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct SyncCommitteePoseidonHashesResponse {
+    pub sync_committee_poseidon_hashes: Vec<SyncCommitteePoseidonHashEntry>,
+}
+
+#[cw_serde]
+pub struct NearestFinalizedSlotResponse {
+    pub slot: Option<u64>,
+}
+
 #[cw_serde]
 pub struct VerifyLogEntryResponse {
     pub verified: bool,
+    /// Slot the receipts root was checked against, `None` if the header check
+    /// was skipped or no slot at or after `tx_slot` is stored
+    pub src_slot: Option<u64>,
+    /// Why the log entry was not verified, `None` if it was
+    pub failure_reason: Option<VerificationFailure>,
+}
+
+#[cw_serde]
+pub enum VerificationFailure {
+    /// Log entry or receipt don't decode, or the log is not at `log_index` in the receipt
+    LogMismatch,
+    /// Receipt is not proven by the trie proof against the receipts root
+    TrieProofInvalid,
+    /// No header root is stored for the source slot
+    HeaderRootUnknown,
+    /// Receipts root or withdrawal is not proven by the branch against the source header root
+    BeaconBranchInvalid,
+    /// Header root of the source slot fell out of the retention window
+    SlotPruned,
+    /// Source header root doesn't meet `min_participation` or `min_age`
+    RequirementsNotMet,
+    /// Transaction slot is before Capella, or after the source slot or the
+    /// head if no source slot is given
+    SlotRangeInvalid,
+    /// A root or branch sibling isn't 32 bytes, or the branch length doesn't
+    /// match the depth of the proven field
+    MalformedProof,
+}
+
+#[cw_serde]
+pub struct TransactionInfo {
+    /// 0 for legacy transactions
+    pub tx_type: u8,
+    /// Unset for legacy transactions without replay protection
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    /// Unset for contract creations
+    pub to: Option<Vec<u8>>,
+    pub value: Uint256,
+    pub input: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct VerifyTransactionResponse {
+    pub src_slot: u64,
+    pub block_number: u64,
+    pub transaction: TransactionInfo,
+}
+
+#[cw_serde]
+pub struct ExecutionHeaderInfo {
+    pub block_hash: Vec<u8>,
+    pub parent_hash: Vec<u8>,
+    pub number: u64,
+    pub timestamp: u64,
+    /// Unset before London
+    pub base_fee_per_gas: Option<u64>,
+    pub gas_limit: Uint256,
+    pub gas_used: Uint256,
+    pub state_root: Vec<u8>,
+    pub transactions_root: Vec<u8>,
+    pub receipts_root: Vec<u8>,
+    /// Unset before Shanghai
+    pub withdrawals_root: Option<Vec<u8>>,
+    /// Unset before Cancun
+    pub parent_beacon_block_root: Option<Vec<u8>>,
+}
+
+#[cw_serde]
+pub struct VerifyExecutionHeaderResponse {
+    pub src_slot: u64,
+    pub header: ExecutionHeaderInfo,
+}
+
+#[cw_serde]
+pub struct VerifyWithdrawalResponse {
+    pub verified: bool,
+    /// Slot whose header root was checked, unset if none is stored at or after `tx_slot`
+    pub src_slot: Option<u64>,
+    /// Why the withdrawal was not verified, `None` if it was
+    pub failure_reason: Option<VerificationFailure>,
+}
+
+#[cw_serde]
+pub struct AccountState {
+    pub nonce: u64,
+    pub balance: Uint256,
+    pub storage_root: Vec<u8>,
+    pub code_hash: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct VerifyAccountResponse {
+    pub account: AccountState,
+}
+
+#[cw_serde]
+pub struct StorageValue {
+    pub key: Vec<u8>,
+    pub value: Uint256,
+}
+
+#[cw_serde]
+pub struct VerifyStorageResponse {
+    pub account: AccountState,
+    /// In the order of the storage proofs
+    pub values: Vec<StorageValue>,
+}
+
+#[cw_serde]
+pub struct VerifyingKeyInfo {
+    pub version: u64,
+    pub activation_slot: u64,
+    pub vkey_hash: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct VerifyingKeysResponse {
+    pub versions: Vec<VerifyingKeyInfo>,
 }
@@ -1,16 +1,35 @@
 use crate::eth_utility::Network;
-use cosmwasm_std::Addr;
+use crate::msg::{
+    Circuit, FinalizedHeaderInfo, OptimisticConfig, ParticipationConfig, PendingUpdate,
+    RebootstrapRecord, SubscriptionLimits,
+};
+use cosmwasm_std::{Addr, Coin, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
 use serde::{Deserialize, Serialize};
 
 use electron_rs::verifier::near::PreparedVerifyingKey;
 
 const NON_MAPPED_STATE_KEY: &str = "non_mapped";
-const NON_MAPPED_STATE_LC_KEY: &str = "non_mapped_lc";
-const NON_MAPPED_STATE_SC_KEY: &str = "non_mapped_sc";
+const PENDING_ADMIN_KEY: &str = "pending_admin";
+const PAUSED_KEY: &str = "paused";
+const RELAYER_ALLOWLIST_KEY: &str = "relayer_allowlist";
+const RELAYERS: &str = "relayers";
+const RETENTION_SLOTS_KEY: &str = "retention_slots";
+const PARTICIPATION_CONFIG_KEY: &str = "participation_config";
+const OPTIMISTIC_CONFIG_KEY: &str = "optimistic_config";
+const PENDING_UPDATES: &str = "pending_updates";
+const REBOOTSTRAPS: &str = "rebootstraps";
+const SUBSCRIPTION_LIMITS_KEY: &str = "subscription_limits";
+const NEXT_SUBSCRIBER_ID_KEY: &str = "next_subscriber_id";
+const SUBSCRIBERS: &str = "subscribers";
+const REWARD_KEY: &str = "reward";
+const REWARD_POOL_KEY: &str = "reward_pool";
 const HEADER_ROOTS: &str = "header_roots";
+const FINALIZED_HEADER_INFOS: &str = "finalized_header_infos";
 const EXECUTION_STATE_ROOTS: &str = "execution_state_roots";
 const SYNC_COMMITTEE_POSEIDON_HASHES: &str = "sync_committee_poseidon_hashes";
+const LC_UPDATE_VKEYS: &str = "lc_update_vkeys";
+const SC_UPDATE_VKEYS: &str = "sc_update_vkeys";
 
 pub struct ContractState<'a> {
     // state that is store in maps
@@ -18,13 +37,32 @@ pub struct ContractState<'a> {
 
     // state that is not stored in maps
     pub non_mapped: Item<'a, NonMappedState>,
-    pub non_mapped_lc: Item<'a, NonMappedStateLC>,
-    pub non_mapped_sc: Item<'a, NonMappedStateSC>,
+    /// Admin proposed by the current admin, waiting to accept
+    pub pending_admin: Item<'a, Addr>,
+    /// Light client updates are rejected while set
+    pub paused: Item<'a, bool>,
+    /// Only allowlisted relayers can submit updates while set
+    pub relayer_allowlist: Item<'a, bool>,
+    /// Roots older than this many slots behind the head are pruned
+    pub retention_slots: Item<'a, u64>,
+    /// Participation threshold of updates, mainnet values if unset
+    pub participation_config: Item<'a, ParticipationConfig>,
+    /// Bond and challenge period of optimistic updates, disabled if unset
+    pub optimistic_config: Item<'a, OptimisticConfig>,
+    /// Subscriber count and callback gas limits, defaults if unset
+    pub subscription_limits: Item<'a, SubscriptionLimits>,
+    /// Id of the next subscriber, also used as reply id of its callbacks
+    pub next_subscriber_id: Item<'a, u64>,
+    /// Reward paid per accepted update
+    pub reward: Item<'a, Coin>,
+    /// Funds held for rewards, in the reward denom
+    pub reward_pool: Item<'a, Uint128>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct NonMappedState {
-    pub admin: Addr,
+    /// Contract admin, `None` once renounced
+    pub admin: Option<Addr>,
     /// Network. e.g. mainnet, goerli,
     pub network: Network,
     /// Latest head slot
@@ -32,24 +70,47 @@ pub struct NonMappedState {
 }
 
 #[derive(Serialize, Deserialize)]
-pub struct NonMappedStateLC {
-    /// lc_update circuit verification key
-    pub vkey_lc_update: PreparedVerifyingKey,
+pub struct VerifyingKeyVersion {
+    /// Version number, starting at 1 for the key set at init
+    pub version: u64,
+    /// First attested slot verified with this key
+    pub activation_slot: u64,
+    /// sha256 of the serialized prepared verifying key
+    pub vkey_hash: Vec<u8>,
+    /// Circuit verification key
+    pub vkey: PreparedVerifyingKey,
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct NonMappedStateSC {
-    /// sc_update circuit verification key
-    pub vkey_sc_update: PreparedVerifyingKey,
+impl<'a> MappedState<'a> {
+    pub fn vkeys(&self, circuit: &Circuit) -> &Map<'a, u64, VerifyingKeyVersion> {
+        match circuit {
+            Circuit::LcUpdate => &self.lc_update_vkeys,
+            Circuit::ScUpdate => &self.sc_update_vkeys,
+        }
+    }
 }
 
 pub struct MappedState<'a> {
     /// Beacon block header roots  mapped to slot numbers
     pub header_roots: Map<'a, u64, Vec<u8>>,
+    /// Provenance of the header roots mapped to slot numbers
+    pub finalized_header_infos: Map<'a, u64, FinalizedHeaderInfo>,
     /// Execution state roots mapped to slot numbers
     pub execution_state_roots: Map<'a, u64, Vec<u8>>,
     /// Sync committee public keys poseidon hash mapped to period
     pub sync_committee_poseidon_hashes: Map<'a, u64, Vec<u8>>,
+    /// lc_update circuit verification keys mapped to activation slot
+    pub lc_update_vkeys: Map<'a, u64, VerifyingKeyVersion>,
+    /// sc_update circuit verification keys mapped to activation slot
+    pub sc_update_vkeys: Map<'a, u64, VerifyingKeyVersion>,
+    /// Relayers allowed to submit updates in allowlist mode
+    pub relayers: Map<'a, &'a Addr, Empty>,
+    /// Optimistic updates in their challenge period mapped to finalized slot
+    pub pending_updates: Map<'a, u64, PendingUpdate>,
+    /// Re-bootstrap audit trail mapped to sequence number
+    pub rebootstraps: Map<'a, u64, RebootstrapRecord>,
+    /// Contracts notified when the head advances mapped to subscriber id
+    pub subscribers: Map<'a, u64, Addr>,
 }
 
 #[allow(clippy::new_without_default)]
@@ -57,13 +118,62 @@ impl ContractState<'_> {
     pub fn new() -> Self {
         Self {
             non_mapped: Item::new(NON_MAPPED_STATE_KEY),
-            non_mapped_lc: Item::new(NON_MAPPED_STATE_LC_KEY),
-            non_mapped_sc: Item::new(NON_MAPPED_STATE_SC_KEY),
+            pending_admin: Item::new(PENDING_ADMIN_KEY),
+            paused: Item::new(PAUSED_KEY),
+            relayer_allowlist: Item::new(RELAYER_ALLOWLIST_KEY),
+            retention_slots: Item::new(RETENTION_SLOTS_KEY),
+            participation_config: Item::new(PARTICIPATION_CONFIG_KEY),
+            optimistic_config: Item::new(OPTIMISTIC_CONFIG_KEY),
+            subscription_limits: Item::new(SUBSCRIPTION_LIMITS_KEY),
+            next_subscriber_id: Item::new(NEXT_SUBSCRIBER_ID_KEY),
+            reward: Item::new(REWARD_KEY),
+            reward_pool: Item::new(REWARD_POOL_KEY),
             mapped: MappedState {
                 header_roots: Map::new(HEADER_ROOTS),
+                finalized_header_infos: Map::new(FINALIZED_HEADER_INFOS),
                 execution_state_roots: Map::new(EXECUTION_STATE_ROOTS),
                 sync_committee_poseidon_hashes: Map::new(SYNC_COMMITTEE_POSEIDON_HASHES),
+                lc_update_vkeys: Map::new(LC_UPDATE_VKEYS),
+                sc_update_vkeys: Map::new(SC_UPDATE_VKEYS),
+                relayers: Map::new(RELAYERS),
+                pending_updates: Map::new(PENDING_UPDATES),
+                rebootstraps: Map::new(REBOOTSTRAPS),
+                subscribers: Map::new(SUBSCRIBERS),
             },
         }
     }
 }
+
+/// Storage layout of v0.1.0, only read when migrating older deployments
+pub mod v0_1_0 {
+    use crate::eth_utility::Network;
+    use cosmwasm_std::Addr;
+    use cw_storage_plus::Item;
+    use electron_rs::verifier::near::PreparedVerifyingKey;
+    use serde::{Deserialize, Serialize};
+
+    pub const NON_MAPPED: Item<NonMappedState> = Item::new(super::NON_MAPPED_STATE_KEY);
+    pub const NON_MAPPED_LC: Item<NonMappedStateLC> = Item::new("non_mapped_lc");
+    pub const NON_MAPPED_SC: Item<NonMappedStateSC> = Item::new("non_mapped_sc");
+
+    #[derive(Serialize, Deserialize)]
+    pub struct NonMappedState {
+        pub admin: Addr,
+        /// Network. e.g. mainnet, goerli,
+        pub network: Network,
+        /// Latest head slot
+        pub head_slot: u64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct NonMappedStateLC {
+        /// lc_update circuit verification key
+        pub vkey_lc_update: PreparedVerifyingKey,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct NonMappedStateSC {
+        /// sc_update circuit verification key
+        pub vkey_sc_update: PreparedVerifyingKey,
+    }
+}
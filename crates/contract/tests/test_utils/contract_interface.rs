@@ -1,19 +1,102 @@
+use cosmwasm_std::{Addr, Coin, Event};
 use cw_eth2_lc::msg::{
-    ExecutionStateRootResponse, HeadResponse, HeaderRootResponse, LightClientUpdate,
-    SyncCommitteePoseidonHashResponse,
+    AdminResponse, Circuit, ConfigResponse, ExecuteMsg, ExecutionStateRootResponse,
+    ExecutionStateRootsResponse, FinalizedHeaderInfoResponse, HeadResponse, HeaderRootResponse,
+    HeaderRootsResponse, LightClientUpdate, NearestFinalizedSlotResponse,
+    OldestRetainedSlotResponse, ParticipationConfigResponse, PendingUpdateResponse, RangeOrder,
+    RebootstrapHistoryResponse, RelayersResponse, RewardBalanceResponse, RewardConfigResponse,
+    StatusResponse, SubscribersResponse, SudoMsg, SyncCommitteePoseidonHashResponse,
+    SyncCommitteePoseidonHashesResponse, VerifyAccountRequest, VerifyAccountResponse,
+    VerifyExecutionHeaderRequest, VerifyExecutionHeaderResponse, VerifyLogEntryRequest,
+    VerifyLogEntryResponse, VerifyStorageRequest, VerifyStorageResponse, VerifyTransactionRequest,
+    VerifyTransactionResponse, VerifyWithdrawalRequest, VerifyWithdrawalResponse,
+    VerifyingKeysResponse,
 };
 use cw_eth2_lc::Result;
 
 pub trait ContractInterface {
     // Execute
     fn update_light_client(&mut self, light_client_update: LightClientUpdate) -> Result<()>;
+    /// Returns the events emitted by the contract
+    fn execute(&mut self, sender: Addr, msg: ExecuteMsg) -> Result<Vec<Event>> {
+        self.execute_with_funds(sender, msg, &[])
+    }
+    fn sudo(&mut self, msg: SudoMsg) -> Result<Vec<Event>>;
+    fn execute_with_funds(
+        &mut self,
+        sender: Addr,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> Result<Vec<Event>>;
+    /// Deploys a contract accepting any execute message, e.g. to subscribe
+    fn instantiate_subscriber(&mut self) -> Addr;
 
     // Query
     fn head(&self) -> Result<HeadResponse>;
+    fn status(&self, max_lag: Option<u64>) -> Result<StatusResponse>;
+    fn config(&self) -> Result<ConfigResponse>;
+    fn admin(&self) -> Result<AdminResponse>;
+    fn relayers(&self) -> Result<RelayersResponse>;
+    fn reward_config(&self) -> Result<RewardConfigResponse>;
+    fn reward_balance(&self) -> Result<RewardBalanceResponse>;
+    fn oldest_retained_slot(&self) -> Result<OldestRetainedSlotResponse>;
+    fn participation_config(&self) -> Result<ParticipationConfigResponse>;
+    fn pending_update(&self, finalized_slot: u64) -> Result<PendingUpdateResponse>;
     fn header_root(&self, slot: u64) -> Result<HeaderRootResponse>;
+    fn finalized_header_info(&self, slot: u64) -> Result<FinalizedHeaderInfoResponse>;
+    fn nearest_finalized_slot(
+        &self,
+        at_or_after: Option<u64>,
+        at_or_before: Option<u64>,
+    ) -> Result<NearestFinalizedSlotResponse>;
     fn execution_state_root(&self, slot: u64) -> Result<ExecutionStateRootResponse>;
     fn sync_committee_poseidon_hash(
         &self,
         period: u64,
     ) -> Result<SyncCommitteePoseidonHashResponse>;
+    fn header_roots(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<HeaderRootsResponse>;
+    fn execution_state_roots(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<ExecutionStateRootsResponse>;
+    fn sync_committee_poseidon_hashes(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<SyncCommitteePoseidonHashesResponse>;
+    fn verify_log_entry(
+        &self,
+        verify_log_entry_request: VerifyLogEntryRequest,
+    ) -> Result<VerifyLogEntryResponse>;
+    fn verify_transaction(
+        &self,
+        verify_transaction_request: VerifyTransactionRequest,
+    ) -> Result<VerifyTransactionResponse>;
+    fn verify_execution_header(
+        &self,
+        verify_execution_header_request: VerifyExecutionHeaderRequest,
+    ) -> Result<VerifyExecutionHeaderResponse>;
+    fn verify_withdrawal(
+        &self,
+        verify_withdrawal_request: VerifyWithdrawalRequest,
+    ) -> Result<VerifyWithdrawalResponse>;
+    fn verify_account(
+        &self,
+        verify_account_request: VerifyAccountRequest,
+    ) -> Result<VerifyAccountResponse>;
+    fn verify_storage(
+        &self,
+        verify_storage_request: VerifyStorageRequest,
+    ) -> Result<VerifyStorageResponse>;
+    fn verifying_keys(&self, circuit: Circuit) -> Result<VerifyingKeysResponse>;
+    fn rebootstrap_history(&self) -> Result<RebootstrapHistoryResponse>;
+    fn subscribers(&self) -> Result<SubscribersResponse>;
 }
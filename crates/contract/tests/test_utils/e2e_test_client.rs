@@ -1,13 +1,25 @@
 use super::contract_interface::ContractInterface;
-use crate::test_utils::accounts;
-use cosmwasm_std::{Addr, CosmosMsg, QueryRequest};
-use cw_eth2_lc::entrypoint::{execute, instantiate, query};
+use crate::test_utils::{accounts, test_env, TEST_DENOM};
+use cosmwasm_std::{
+    coins, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+    QueryRequest, Response, StdError, StdResult,
+};
+use cw_eth2_lc::entrypoint::{execute, instantiate, query, reply, sudo};
 use cw_eth2_lc::msg::{
-    ExecuteMsg, ExecutionStateRootResponse, HeadResponse, HeaderRootResponse, InitInput,
-    InstantiateMsg, LightClientUpdate, QueryMsg, SyncCommitteePoseidonHashResponse,
+    AdminResponse, Circuit, ConfigResponse, ExecuteMsg, ExecutionStateRootResponse,
+    ExecutionStateRootsResponse, FinalizedHeaderInfoResponse, HeadResponse, HeaderRootResponse,
+    HeaderRootsResponse, InitInput, InstantiateMsg, LightClientUpdate,
+    NearestFinalizedSlotResponse, OldestRetainedSlotResponse, ParticipationConfigResponse,
+    PendingUpdateResponse, QueryMsg, RangeOrder, RebootstrapHistoryResponse, RelayersResponse,
+    RewardBalanceResponse, RewardConfigResponse, StatusResponse, SubscribersResponse, SudoMsg,
+    SyncCommitteePoseidonHashResponse, SyncCommitteePoseidonHashesResponse, VerifyAccountRequest,
+    VerifyAccountResponse, VerifyExecutionHeaderRequest, VerifyExecutionHeaderResponse,
+    VerifyLogEntryRequest, VerifyLogEntryResponse, VerifyStorageRequest, VerifyStorageResponse,
+    VerifyTransactionRequest, VerifyTransactionResponse, VerifyWithdrawalRequest,
+    VerifyWithdrawalResponse, VerifyingKeysResponse,
 };
 use cw_eth2_lc::Result;
-use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
@@ -18,8 +30,18 @@ pub struct E2ETestContractImplementation {
 
 impl E2ETestContractImplementation {
     pub fn new(args: InitInput) -> Result<Self> {
-        let mut app = App::default();
-        let code = ContractWrapper::new(execute, instantiate, query);
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            for id in 0..6 {
+                router
+                    .bank
+                    .init_balance(storage, &accounts(id), coins(1_000_000, TEST_DENOM))
+                    .unwrap();
+            }
+        });
+        let code = ContractWrapper::new(execute, instantiate, query)
+            .with_sudo(sudo)
+            .with_reply(reply);
+        app.update_block(|block| block.time = test_env().block.time);
         let code_id = app.store_code(Box::new(code));
         println!("{:?}", code_id);
         let contract_addr = app.instantiate_contract(
@@ -57,14 +79,91 @@ impl ContractInterface for E2ETestContractImplementation {
         Ok(())
     }
 
+    fn execute_with_funds(
+        &mut self,
+        sender: Addr,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> Result<Vec<Event>> {
+        let res = self
+            .app
+            .execute_contract(sender, self.contract_addr.clone(), &msg, funds)?;
+
+        Ok(res.events)
+    }
+
+    fn sudo(&mut self, msg: SudoMsg) -> Result<Vec<Event>> {
+        let res = self.app.wasm_sudo(self.contract_addr.clone(), &msg)?;
+        Ok(res.events)
+    }
+
+    fn instantiate_subscriber(&mut self) -> Addr {
+        let code = ContractWrapper::new(accept_execute, accept_execute, reject_query);
+        let code_id = self.app.store_code(Box::new(code));
+        self.app
+            .instantiate_contract(code_id, accounts(0), &Empty {}, &[], "Subscriber", None)
+            .unwrap()
+    }
+
     fn head(&self) -> Result<HeadResponse> {
         self.query_smart_contract(QueryMsg::Head {})
     }
 
+    fn status(&self, max_lag: Option<u64>) -> Result<StatusResponse> {
+        self.query_smart_contract(QueryMsg::Status { max_lag })
+    }
+
+    fn config(&self) -> Result<ConfigResponse> {
+        self.query_smart_contract(QueryMsg::Config {})
+    }
+
+    fn admin(&self) -> Result<AdminResponse> {
+        self.query_smart_contract(QueryMsg::Admin {})
+    }
+
+    fn relayers(&self) -> Result<RelayersResponse> {
+        self.query_smart_contract(QueryMsg::Relayers {})
+    }
+
+    fn reward_config(&self) -> Result<RewardConfigResponse> {
+        self.query_smart_contract(QueryMsg::RewardConfig {})
+    }
+
+    fn reward_balance(&self) -> Result<RewardBalanceResponse> {
+        self.query_smart_contract(QueryMsg::RewardBalance {})
+    }
+
+    fn oldest_retained_slot(&self) -> Result<OldestRetainedSlotResponse> {
+        self.query_smart_contract(QueryMsg::OldestRetainedSlot {})
+    }
+
+    fn participation_config(&self) -> Result<ParticipationConfigResponse> {
+        self.query_smart_contract(QueryMsg::ParticipationConfig {})
+    }
+
+    fn pending_update(&self, finalized_slot: u64) -> Result<PendingUpdateResponse> {
+        self.query_smart_contract(QueryMsg::PendingUpdate { finalized_slot })
+    }
+
     fn header_root(&self, slot: u64) -> Result<HeaderRootResponse> {
         self.query_smart_contract(QueryMsg::HeaderRoot { slot })
     }
 
+    fn finalized_header_info(&self, slot: u64) -> Result<FinalizedHeaderInfoResponse> {
+        self.query_smart_contract(QueryMsg::FinalizedHeaderInfo { slot })
+    }
+
+    fn nearest_finalized_slot(
+        &self,
+        at_or_after: Option<u64>,
+        at_or_before: Option<u64>,
+    ) -> Result<NearestFinalizedSlotResponse> {
+        self.query_smart_contract(QueryMsg::NearestFinalizedSlot {
+            at_or_after,
+            at_or_before,
+        })
+    }
+
     fn execution_state_root(&self, slot: u64) -> Result<ExecutionStateRootResponse> {
         self.query_smart_contract(QueryMsg::ExecutionStateRoot { slot })
     }
@@ -75,4 +174,117 @@ impl ContractInterface for E2ETestContractImplementation {
     ) -> Result<SyncCommitteePoseidonHashResponse> {
         self.query_smart_contract(QueryMsg::SyncCommitteePoseidonHash { period })
     }
+
+    fn header_roots(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<HeaderRootsResponse> {
+        self.query_smart_contract(QueryMsg::HeaderRoots {
+            start_after,
+            limit,
+            order,
+        })
+    }
+
+    fn execution_state_roots(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<ExecutionStateRootsResponse> {
+        self.query_smart_contract(QueryMsg::ExecutionStateRoots {
+            start_after,
+            limit,
+            order,
+        })
+    }
+
+    fn sync_committee_poseidon_hashes(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<SyncCommitteePoseidonHashesResponse> {
+        self.query_smart_contract(QueryMsg::SyncCommitteePoseidonHashes {
+            start_after,
+            limit,
+            order,
+        })
+    }
+
+    fn verify_log_entry(
+        &self,
+        verify_log_entry_request: VerifyLogEntryRequest,
+    ) -> Result<VerifyLogEntryResponse> {
+        self.query_smart_contract(QueryMsg::VerifyLogEntry {
+            verify_log_entry_request,
+        })
+    }
+
+    fn verify_transaction(
+        &self,
+        verify_transaction_request: VerifyTransactionRequest,
+    ) -> Result<VerifyTransactionResponse> {
+        self.query_smart_contract(QueryMsg::VerifyTransaction {
+            verify_transaction_request,
+        })
+    }
+
+    fn verify_execution_header(
+        &self,
+        verify_execution_header_request: VerifyExecutionHeaderRequest,
+    ) -> Result<VerifyExecutionHeaderResponse> {
+        self.query_smart_contract(QueryMsg::VerifyExecutionHeader {
+            verify_execution_header_request,
+        })
+    }
+
+    fn verify_withdrawal(
+        &self,
+        verify_withdrawal_request: VerifyWithdrawalRequest,
+    ) -> Result<VerifyWithdrawalResponse> {
+        self.query_smart_contract(QueryMsg::VerifyWithdrawal {
+            verify_withdrawal_request,
+        })
+    }
+
+    fn verify_account(
+        &self,
+        verify_account_request: VerifyAccountRequest,
+    ) -> Result<VerifyAccountResponse> {
+        self.query_smart_contract(QueryMsg::VerifyAccount {
+            verify_account_request,
+        })
+    }
+
+    fn verify_storage(
+        &self,
+        verify_storage_request: VerifyStorageRequest,
+    ) -> Result<VerifyStorageResponse> {
+        self.query_smart_contract(QueryMsg::VerifyStorage {
+            verify_storage_request,
+        })
+    }
+
+    fn verifying_keys(&self, circuit: Circuit) -> Result<VerifyingKeysResponse> {
+        self.query_smart_contract(QueryMsg::VerifyingKeys { circuit })
+    }
+
+    fn rebootstrap_history(&self) -> Result<RebootstrapHistoryResponse> {
+        self.query_smart_contract(QueryMsg::RebootstrapHistory {})
+    }
+
+    fn subscribers(&self) -> Result<SubscribersResponse> {
+        self.query_smart_contract(QueryMsg::Subscribers {})
+    }
+}
+
+fn accept_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn reject_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("no queries"))
 }
@@ -1,9 +1,18 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{Addr, Empty, Env, OwnedDeps, Timestamp};
+use cw_eth2_lc::contract::instantiate::CONTRACT_NAME;
+use cw_eth2_lc::eth_utility::{compute_sync_committee_period, Network, NetworkConfig};
 use cw_eth2_lc::msg::{InitInput, LightClientUpdate};
+use cw_eth2_lc::state::{v0_1_0, ContractState};
+use cw_eth2_lc::Result;
+use electron_rs::verifier::near::{get_prepared_verifying_key, parse_verification_key};
 use lazy_static::lazy_static;
+use std::str::FromStr;
 pub mod contract_interface;
 pub mod e2e_test_client;
+pub mod proofs;
 pub mod test_context;
+pub mod trie;
 pub mod unit_test_client;
 
 pub fn read_client_update(filename: String) -> LightClientUpdate {
@@ -14,11 +23,32 @@ pub fn read_init_input(filename: String) -> InitInput {
     serde_json::from_reader(std::fs::File::open(std::path::Path::new(&filename)).unwrap()).unwrap()
 }
 
+/// Native denom test accounts are funded with
+pub const TEST_DENOM: &str = "uatom";
+
+/// Mock env timed an hour after the newest attested slot of the test data
+pub fn test_env() -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(
+        NetworkConfig::new(&Network::Goerli).compute_timestamp_at_slot(6514104) + 3600,
+    );
+    env
+}
+
 /// Returns a pre-defined account_id from a list of 6.
 pub fn accounts(id: usize) -> Addr {
     Addr::unchecked(["alice", "bob", "charlie", "danny", "eugene", "fargo"][id].to_string())
 }
 
+/// Asserts that a contract call failed and that the error message contains `expected`.
+pub fn assert_err_contains<T: std::fmt::Debug>(res: Result<T>, expected: &str) {
+    let err = format!("{:#}", res.unwrap_err());
+    assert!(
+        err.contains(expected),
+        "expected error containing {expected:?}, got {err:?}"
+    );
+}
+
 pub fn read_client_updates(lc_update: bool, slots: Vec<u64>) -> Vec<LightClientUpdate> {
     let mut updates = vec![];
     for slot in slots {
@@ -48,3 +78,65 @@ pub fn get_test_data() -> (
 
     (init_input, &LC_UPDATES, &SC_UPDATE_UPDATES)
 }
+
+/// Storage as left behind by instantiating v0.1.0 of the contract with `init_input`
+pub fn v0_1_0_deps(init_input: InitInput) -> OwnedDeps<MockStorage, MockApi, MockQuerier, Empty> {
+    let mut deps = mock_dependencies();
+    let storage = deps.as_mut().storage;
+    let state = ContractState::new();
+    let prepare_vkey =
+        |vkey_string| get_prepared_verifying_key(parse_verification_key(vkey_string).unwrap());
+
+    cw2::set_contract_version(storage, CONTRACT_NAME, "0.1.0").unwrap();
+    v0_1_0::NON_MAPPED
+        .save(
+            storage,
+            &v0_1_0::NonMappedState {
+                admin: init_input.admin,
+                network: Network::from_str(&init_input.network).unwrap(),
+                head_slot: init_input.head_slot,
+            },
+        )
+        .unwrap();
+    v0_1_0::NON_MAPPED_LC
+        .save(
+            storage,
+            &v0_1_0::NonMappedStateLC {
+                vkey_lc_update: prepare_vkey(init_input.vkey_lc_update_string),
+            },
+        )
+        .unwrap();
+    v0_1_0::NON_MAPPED_SC
+        .save(
+            storage,
+            &v0_1_0::NonMappedStateSC {
+                vkey_sc_update: prepare_vkey(init_input.vkey_sc_update_string),
+            },
+        )
+        .unwrap();
+    state
+        .mapped
+        .header_roots
+        .save(storage, init_input.head_slot, &init_input.header_root)
+        .unwrap();
+    state
+        .mapped
+        .execution_state_roots
+        .save(
+            storage,
+            init_input.head_slot,
+            &init_input.execution_state_root,
+        )
+        .unwrap();
+    state
+        .mapped
+        .sync_committee_poseidon_hashes
+        .save(
+            storage,
+            compute_sync_committee_period(init_input.head_slot),
+            &init_input.sync_committee_poseidon_hash,
+        )
+        .unwrap();
+
+    deps
+}
@@ -0,0 +1,178 @@
+use cw_eth2_lc::msg::{Checkpoint, ExecuteMsg};
+use rlp::RlpStream;
+use sha2::{Digest, Sha256};
+
+use super::contract_interface::ContractInterface;
+use super::{accounts, get_test_data};
+
+pub const RECEIPTS_ROOT_FIELD: u128 = 3;
+pub const BLOCK_HASH_FIELD: u128 = 12;
+pub const WITHDRAWALS_ROOT_FIELD: u128 = 14;
+
+/// Generalized index of an execution payload field in a beacon block header,
+/// through the beacon state root
+pub fn execution_payload_index(field: u128) -> u128 {
+    11 * 2u128.pow(9) + 384 + field
+}
+
+/// Header root over `leaf` at generalized index `index` with `branch` as siblings
+pub fn merkle_root(leaf: Vec<u8>, index: u128, branch: &[Vec<u8>]) -> Vec<u8> {
+    branch
+        .iter()
+        .enumerate()
+        .fold(leaf, |value, (depth, sibling)| {
+            let pair = if (index >> depth) & 1 == 0 {
+                [value, sibling.clone()]
+            } else {
+                [sibling.clone(), value]
+            };
+            Sha256::digest(pair.concat()).to_vec()
+        })
+}
+
+/// Arbitrary siblings for a branch `depth` levels deep
+pub fn fake_branch(depth: u8) -> Vec<Vec<u8>> {
+    (0..depth).map(|i| vec![i; 32]).collect()
+}
+
+/// Beacon forks with distinct beacon state or execution payload header layouts
+#[derive(Debug, Clone, Copy)]
+pub enum Fork {
+    Capella,
+    Deneb,
+    Electra,
+}
+
+impl Fork {
+    fn beacon_state_fields(self) -> usize {
+        match self {
+            Fork::Capella | Fork::Deneb => 28,
+            Fork::Electra => 37,
+        }
+    }
+
+    fn execution_payload_header_fields(self) -> usize {
+        match self {
+            Fork::Capella => 15,
+            Fork::Deneb | Fork::Electra => 17,
+        }
+    }
+}
+
+/// Merkle root of the `fields` roots of an SSZ container or vector, padded
+/// with zero chunks to a power of two, and the branch of field `index`
+pub fn container_proof(mut fields: Vec<Vec<u8>>, mut index: usize) -> (Vec<u8>, Vec<Vec<u8>>) {
+    fields.resize(fields.len().next_power_of_two(), vec![0; 32]);
+    let mut branch = vec![];
+    while fields.len() > 1 {
+        branch.push(fields[index ^ 1].clone());
+        fields = fields
+            .chunks(2)
+            .map(|pair| Sha256::digest(pair.concat()).to_vec())
+            .collect();
+        index /= 2;
+    }
+
+    (fields.remove(0), branch)
+}
+
+/// Container of `count` arbitrary field roots with `leaf` as field `index`
+fn container_with(count: usize, index: usize, leaf: Vec<u8>) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let mut fields = (0..count)
+        .map(|i| vec![0x80 | i as u8; 32])
+        .collect::<Vec<_>>();
+    fields[index] = leaf;
+    container_proof(fields, index)
+}
+
+/// Root of a beacon state of `fork` with `leaf` as field `field`, and its branch
+pub fn beacon_state_proof(fork: Fork, field: usize, leaf: Vec<u8>) -> (Vec<u8>, Vec<Vec<u8>>) {
+    container_with(fork.beacon_state_fields(), field, leaf)
+}
+
+/// Root of a beacon state of `fork` whose latest execution payload header has
+/// `leaf` as field `field`, and the branch from the payload field up
+pub fn execution_payload_proof(fork: Fork, field: u128, leaf: Vec<u8>) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let (payload_root, payload_branch) =
+        container_with(fork.execution_payload_header_fields(), field as usize, leaf);
+    let (state_root, state_branch) = beacon_state_proof(fork, 24, payload_root);
+    (state_root, [payload_branch, state_branch].concat())
+}
+
+/// Root of a beacon block header over `state_root`, and the branch from the state root up
+pub fn beacon_header_proof(state_root: Vec<u8>) -> (Vec<u8>, Vec<Vec<u8>>) {
+    container_with(5, 3, state_root)
+}
+
+/// Header root over `leaf` as execution payload field `field` of a block of
+/// `fork`, with the branch proving it. The branch follows the containers of
+/// the fork rather than the generalized index the contract computes.
+pub fn same_slot_payload_proof(fork: Fork, field: u128, leaf: Vec<u8>) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let (state_root, state_branch) = execution_payload_proof(fork, field, leaf);
+    let (header_root, header_branch) = beacon_header_proof(state_root);
+    (header_root, [state_branch, header_branch].concat())
+}
+
+/// Rebootstraps to the slot after the test data head with the given roots,
+/// so proofs can be checked against them. Returns the slot.
+pub fn seed_roots(
+    contract: &mut Box<dyn ContractInterface>,
+    header_root: Vec<u8>,
+    execution_state_root: Vec<u8>,
+) -> u64 {
+    let (init_input, _, _) = get_test_data();
+    let slot = init_input.head_slot + 1;
+    seed_roots_at(contract, slot, header_root, execution_state_root);
+
+    slot
+}
+
+/// Rebootstraps to `slot` with the given roots
+pub fn seed_roots_at(
+    contract: &mut Box<dyn ContractInterface>,
+    slot: u64,
+    header_root: Vec<u8>,
+    execution_state_root: Vec<u8>,
+) {
+    let (init_input, _, _) = get_test_data();
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::Rebootstrap {
+                checkpoint: Checkpoint {
+                    head_slot: slot,
+                    header_root,
+                    execution_state_root,
+                    sync_committee_poseidon_hash: init_input.sync_committee_poseidon_hash,
+                },
+            },
+        )
+        .unwrap();
+}
+
+/// Post-Cancun execution block header with the given transactions root
+pub fn cancun_execution_header(transactions_root: &[u8]) -> Vec<u8> {
+    let mut header = RlpStream::new_list(20);
+    header
+        .append(&vec![1u8; 32])
+        .append(&vec![2u8; 32])
+        .append(&vec![3u8; 20])
+        .append(&vec![4u8; 32])
+        .append(&transactions_root)
+        .append(&vec![5u8; 32])
+        .append(&vec![0u8; 256])
+        .append(&0u64)
+        .append(&17_000_000u64)
+        .append(&30_000_000u64)
+        .append(&21_000u64)
+        .append(&1_700_000_000u64)
+        .append(&vec![6u8; 8])
+        .append(&vec![7u8; 32])
+        .append(&vec![0u8; 8])
+        .append(&7u64)
+        .append(&vec![8u8; 32])
+        .append(&131_072u64)
+        .append(&0u64)
+        .append(&vec![9u8; 32]);
+    header.out().to_vec()
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::testing::mock_info;
 use cosmwasm_std::Addr;
 use cw_eth2_lc::contract::Contract;
 use cw_eth2_lc::eth_utility::compute_sync_committee_period;
@@ -8,7 +8,7 @@ use cw_eth2_lc::msg::{
 };
 
 use super::contract_interface::ContractInterface;
-use super::get_test_data;
+use super::{get_test_data, test_env};
 
 #[cfg(feature = "e2e")]
 use crate::test_utils::e2e_test_client::E2ETestContractImplementation;
@@ -22,20 +22,30 @@ pub struct TestContext<'a, 'b> {
 }
 
 pub fn get_test_context<'a>(contract_caller: Addr) -> TestContext<'static, 'a> {
-    let (init_input, lc_updates, sc_updates) = get_test_data();
+    let (init_input, _, _) = get_test_data();
+    get_test_context_with_input(contract_caller, init_input)
+}
+
+pub fn get_test_context_with_input<'a>(
+    contract_caller: Addr,
+    init_input: InitInput,
+) -> TestContext<'static, 'a> {
+    let (_, lc_updates, sc_updates) = get_test_data();
     let contract = get_test_contract(contract_caller, init_input.clone());
 
     println!("{:?}", contract.head().unwrap());
     assert_eq!(
         contract.head().unwrap(),
         HeadResponse {
-            head: init_input.head_slot
+            head: init_input.head_slot,
+            paused: false,
         }
     );
     assert_eq!(
         contract.header_root(init_input.head_slot).unwrap(),
         HeaderRootResponse {
-            header_root: Some(init_input.header_root)
+            header_root: Some(init_input.header_root),
+            pending_header_root: None,
         }
     );
     assert_eq!(
@@ -66,14 +76,14 @@ fn get_test_contract<'a>(
     init_input: InitInput,
 ) -> Box<dyn ContractInterface + 'a> {
     let contract = Contract::new(
-        mock_env(),
+        test_env(),
         Some(mock_info(contract_caller.to_string().as_str(), &[])),
     );
-    let mut contract = UnitTestContractImplementation {
-        inner: contract,
-        deps: mock_dependencies(),
-    };
-    contract.inner.init(contract.deps.as_mut(), init_input);
+    let mut contract = UnitTestContractImplementation::new(contract);
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input)
+        .unwrap();
     Box::new(contract) as Box<dyn ContractInterface + 'a>
 }
 
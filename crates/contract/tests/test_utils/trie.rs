@@ -0,0 +1,161 @@
+use rlp::RlpStream;
+use std::collections::BTreeMap;
+use types::near_keccak256;
+
+/// Reference Merkle Patricia trie producing roots and `eth_getProof` style
+/// proofs, i.e. the hashed nodes on the path from the root to a key.
+#[derive(Default)]
+pub struct Trie {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Trie {
+    pub fn new(entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| (nibbles(&key), value))
+                .collect(),
+        }
+    }
+
+    /// Trie keyed by the keccak hash of each key, as state and storage tries are
+    pub fn secure(entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) -> Self {
+        Self::new(
+            entries
+                .into_iter()
+                .map(|(key, value)| (near_keccak256(&key).to_vec(), value)),
+        )
+    }
+
+    pub fn root(&self) -> Vec<u8> {
+        near_keccak256(&self.encode(&self.all(), 0)).to_vec()
+    }
+
+    /// Proves `key` is in the trie, or that it is absent if it isn't
+    pub fn proof(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        let key = nibbles(key);
+        let mut entries = self.all();
+        let mut depth = 0;
+        let mut proof = vec![];
+        loop {
+            let node = self.encode(&entries, depth);
+            if proof.is_empty() || node.len() >= 32 {
+                proof.push(node);
+            }
+            if entries.len() <= 1 {
+                return proof;
+            }
+
+            let prefix = common_prefix(&entries, depth);
+            if prefix > 0 {
+                // extension node
+                if key.get(depth..depth + prefix) != Some(&entries[0].0[depth..depth + prefix]) {
+                    return proof;
+                }
+                depth += prefix;
+            } else {
+                // branch node
+                let Some(nibble) = key.get(depth) else {
+                    return proof;
+                };
+                entries.retain(|(path, _)| path.get(depth) == Some(nibble));
+                if entries.is_empty() {
+                    return proof;
+                }
+                depth += 1;
+            }
+        }
+    }
+
+    /// `proof` for the keccak hash of `key`
+    pub fn secure_proof(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        self.proof(&near_keccak256(key))
+    }
+
+    fn all(&self) -> Vec<(&[u8], &[u8])> {
+        self.entries
+            .iter()
+            .map(|(path, value)| (path.as_slice(), value.as_slice()))
+            .collect()
+    }
+
+    fn encode(&self, entries: &[(&[u8], &[u8])], depth: usize) -> Vec<u8> {
+        let mut stream;
+        match entries {
+            [] => return rlp::NULL_RLP.to_vec(),
+            [(path, value)] => {
+                stream = RlpStream::new_list(2);
+                stream
+                    .append(&hex_prefix(&path[depth..], true))
+                    .append(value);
+            }
+            _ => {
+                let prefix = common_prefix(entries, depth);
+                if prefix > 0 {
+                    stream = RlpStream::new_list(2);
+                    stream.append(&hex_prefix(&entries[0].0[depth..depth + prefix], false));
+                    self.append_child(&mut stream, entries, depth + prefix);
+                } else {
+                    stream = RlpStream::new_list(17);
+                    for nibble in 0..16 {
+                        let children = entries
+                            .iter()
+                            .filter(|(path, _)| path.get(depth) == Some(&nibble))
+                            .copied()
+                            .collect::<Vec<_>>();
+                        if children.is_empty() {
+                            stream.append_empty_data();
+                        } else {
+                            self.append_child(&mut stream, &children, depth + 1);
+                        }
+                    }
+                    match entries.iter().find(|(path, _)| path.len() == depth) {
+                        Some((_, value)) => stream.append(value),
+                        None => stream.append_empty_data(),
+                    };
+                }
+            }
+        }
+        stream.out().to_vec()
+    }
+
+    /// Nodes shorter than a hash are embedded in their parent
+    fn append_child(&self, stream: &mut RlpStream, entries: &[(&[u8], &[u8])], depth: usize) {
+        let node = self.encode(entries, depth);
+        if node.len() < 32 {
+            stream.append_raw(&node, 1);
+        } else {
+            stream.append(&near_keccak256(&node).to_vec());
+        }
+    }
+}
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|byte| [byte / 16, byte % 16]).collect()
+}
+
+/// Length of the path all entries share after `depth`, stopping short of an
+/// entry ending there as its value belongs in a branch
+fn common_prefix(entries: &[(&[u8], &[u8])], depth: usize) -> usize {
+    let first = entries[0].0;
+    (0..)
+        .take_while(|&i| {
+            entries
+                .iter()
+                .all(|(path, _)| path.len() > depth + i && path[depth + i] == first[depth + i])
+        })
+        .count()
+}
+
+fn hex_prefix(path: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 2 } else { 0 };
+    let mut encoded = if path.len() % 2 == 1 {
+        vec![(flag + 1) * 16 + path[0]]
+    } else {
+        vec![flag * 16]
+    };
+    let even = &path[path.len() % 2..];
+    encoded.extend(even.chunks(2).map(|pair| pair[0] * 16 + pair[1]));
+    encoded
+}
@@ -1,8 +1,22 @@
-use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
-use cosmwasm_std::{Empty, OwnedDeps};
+use cosmwasm_std::testing::{mock_dependencies, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    to_binary, Addr, Coin, ContractInfoResponse, ContractResult, Empty, Event, OwnedDeps,
+    SystemError, SystemResult, WasmQuery,
+};
 use cw_eth2_lc::contract::Contract;
+use cw_eth2_lc::entrypoint::{execute, sudo};
 use cw_eth2_lc::msg::{
-    ExecutionStateRootResponse, HeadResponse, HeaderRootResponse, SyncCommitteePoseidonHashResponse,
+    AdminResponse, Circuit, ConfigResponse, ExecuteMsg, ExecutionStateRootResponse,
+    ExecutionStateRootsResponse, FinalizedHeaderInfoResponse, HeadResponse, HeaderRootResponse,
+    HeaderRootsResponse, NearestFinalizedSlotResponse, OldestRetainedSlotResponse,
+    ParticipationConfigResponse, PendingUpdateResponse, RangeOrder, RebootstrapHistoryResponse,
+    RelayersResponse, RewardBalanceResponse, RewardConfigResponse, StatusResponse,
+    SubscribersResponse, SudoMsg, SyncCommitteePoseidonHashResponse,
+    SyncCommitteePoseidonHashesResponse, VerifyAccountRequest, VerifyAccountResponse,
+    VerifyExecutionHeaderRequest, VerifyExecutionHeaderResponse, VerifyLogEntryRequest,
+    VerifyLogEntryResponse, VerifyStorageRequest, VerifyStorageResponse, VerifyTransactionRequest,
+    VerifyTransactionResponse, VerifyWithdrawalRequest, VerifyWithdrawalResponse,
+    VerifyingKeysResponse,
 };
 use cw_eth2_lc::Result;
 
@@ -10,13 +24,16 @@ use super::contract_interface::ContractInterface;
 pub struct UnitTestContractImplementation<'a> {
     pub inner: Contract<'a>,
     pub deps: OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>,
+    /// Addresses the mock querier reports as contracts
+    pub contracts: Vec<Addr>,
 }
 
 impl<'a> UnitTestContractImplementation<'a> {
-    pub fn new(contract: Contract<'a>) -> UnitTestContractImplementation {
+    pub fn new(contract: Contract<'a>) -> Self {
         Self {
             inner: contract,
             deps: mock_dependencies(),
+            contracts: vec![],
         }
     }
 }
@@ -27,20 +44,117 @@ impl ContractInterface for UnitTestContractImplementation<'_> {
         light_client_update: cw_eth2_lc::msg::LightClientUpdate,
     ) -> Result<()> {
         self.inner
-            .update_light_client(self.deps.as_mut(), light_client_update);
+            .update_light_client(self.deps.as_mut(), light_client_update)?;
         Ok(())
     }
 
+    fn execute_with_funds(
+        &mut self,
+        sender: Addr,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> Result<Vec<Event>> {
+        let res = execute(
+            self.deps.as_mut(),
+            self.inner.ctx.env.clone(),
+            mock_info(sender.as_str(), funds),
+            msg,
+        )?;
+        Ok(res.events)
+    }
+
+    fn sudo(&mut self, msg: SudoMsg) -> Result<Vec<Event>> {
+        let res = sudo(self.deps.as_mut(), self.inner.ctx.env.clone(), msg)?;
+        Ok(res.events)
+    }
+
+    fn instantiate_subscriber(&mut self) -> Addr {
+        let addr = Addr::unchecked(format!("contract{}", self.contracts.len()));
+        self.contracts.push(addr.clone());
+
+        let contracts = self.contracts.clone();
+        self.deps.querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { contract_addr }
+                if contracts.iter().any(|addr| addr.as_str() == contract_addr) =>
+            {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&ContractInfoResponse::new(1, "alice")).unwrap(),
+                ))
+            }
+            WasmQuery::ContractInfo { contract_addr } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        });
+        addr
+    }
+
     fn head(&self) -> Result<HeadResponse> {
-        Ok(self.inner.head(self.deps.as_ref()))
+        Ok(self.inner.head(self.deps.as_ref())?)
+    }
+
+    fn status(&self, max_lag: Option<u64>) -> Result<StatusResponse> {
+        Ok(self.inner.status(self.deps.as_ref(), max_lag)?)
+    }
+
+    fn config(&self) -> Result<ConfigResponse> {
+        Ok(self.inner.config(self.deps.as_ref())?)
+    }
+
+    fn admin(&self) -> Result<AdminResponse> {
+        Ok(self.inner.admin(self.deps.as_ref())?)
+    }
+
+    fn relayers(&self) -> Result<RelayersResponse> {
+        Ok(self.inner.relayers(self.deps.as_ref())?)
+    }
+
+    fn reward_config(&self) -> Result<RewardConfigResponse> {
+        Ok(self.inner.reward_config(self.deps.as_ref())?)
+    }
+
+    fn reward_balance(&self) -> Result<RewardBalanceResponse> {
+        Ok(self.inner.reward_balance(self.deps.as_ref())?)
+    }
+
+    fn oldest_retained_slot(&self) -> Result<OldestRetainedSlotResponse> {
+        Ok(self.inner.oldest_retained_slot(self.deps.as_ref())?)
+    }
+
+    fn participation_config(&self) -> Result<ParticipationConfigResponse> {
+        Ok(self.inner.participation(self.deps.as_ref())?)
+    }
+
+    fn pending_update(&self, finalized_slot: u64) -> Result<PendingUpdateResponse> {
+        Ok(self
+            .inner
+            .pending_update(self.deps.as_ref(), finalized_slot)?)
     }
 
     fn header_root(&self, slot: u64) -> Result<HeaderRootResponse> {
-        Ok(self.inner.header_root(self.deps.as_ref(), slot))
+        Ok(self.inner.header_root(self.deps.as_ref(), slot)?)
+    }
+
+    fn finalized_header_info(&self, slot: u64) -> Result<FinalizedHeaderInfoResponse> {
+        Ok(self.inner.finalized_header_info(self.deps.as_ref(), slot)?)
+    }
+
+    fn nearest_finalized_slot(
+        &self,
+        at_or_after: Option<u64>,
+        at_or_before: Option<u64>,
+    ) -> Result<NearestFinalizedSlotResponse> {
+        Ok(self
+            .inner
+            .nearest_finalized_slot(self.deps.as_ref(), at_or_after, at_or_before)?)
     }
 
     fn execution_state_root(&self, slot: u64) -> Result<ExecutionStateRootResponse> {
-        Ok(self.inner.execution_state_root(self.deps.as_ref(), slot))
+        Ok(self.inner.execution_state_root(self.deps.as_ref(), slot)?)
     }
 
     fn sync_committee_poseidon_hash(
@@ -49,6 +163,108 @@ impl ContractInterface for UnitTestContractImplementation<'_> {
     ) -> Result<SyncCommitteePoseidonHashResponse> {
         Ok(self
             .inner
-            .sync_committee_poseidon_hash(self.deps.as_ref(), period))
+            .sync_committee_poseidon_hash(self.deps.as_ref(), period)?)
+    }
+
+    fn header_roots(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<HeaderRootsResponse> {
+        Ok(self
+            .inner
+            .header_roots(self.deps.as_ref(), start_after, limit, order)?)
+    }
+
+    fn execution_state_roots(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<ExecutionStateRootsResponse> {
+        Ok(self
+            .inner
+            .execution_state_roots(self.deps.as_ref(), start_after, limit, order)?)
+    }
+
+    fn sync_committee_poseidon_hashes(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    ) -> Result<SyncCommitteePoseidonHashesResponse> {
+        Ok(self.inner.sync_committee_poseidon_hashes(
+            self.deps.as_ref(),
+            start_after,
+            limit,
+            order,
+        )?)
+    }
+
+    fn verify_log_entry(
+        &self,
+        verify_log_entry_request: VerifyLogEntryRequest,
+    ) -> Result<VerifyLogEntryResponse> {
+        Ok(self
+            .inner
+            .verify_log_entry(self.deps.as_ref(), verify_log_entry_request)?)
+    }
+
+    fn verify_transaction(
+        &self,
+        verify_transaction_request: VerifyTransactionRequest,
+    ) -> Result<VerifyTransactionResponse> {
+        Ok(self
+            .inner
+            .verify_transaction(self.deps.as_ref(), verify_transaction_request)?)
+    }
+
+    fn verify_execution_header(
+        &self,
+        verify_execution_header_request: VerifyExecutionHeaderRequest,
+    ) -> Result<VerifyExecutionHeaderResponse> {
+        Ok(self
+            .inner
+            .verify_execution_header(self.deps.as_ref(), verify_execution_header_request)?)
+    }
+
+    fn verify_withdrawal(
+        &self,
+        verify_withdrawal_request: VerifyWithdrawalRequest,
+    ) -> Result<VerifyWithdrawalResponse> {
+        Ok(self
+            .inner
+            .verify_withdrawal(self.deps.as_ref(), verify_withdrawal_request)?)
+    }
+
+    fn verify_account(
+        &self,
+        verify_account_request: VerifyAccountRequest,
+    ) -> Result<VerifyAccountResponse> {
+        Ok(self
+            .inner
+            .verify_account(self.deps.as_ref(), verify_account_request)?)
+    }
+
+    fn verify_storage(
+        &self,
+        verify_storage_request: VerifyStorageRequest,
+    ) -> Result<VerifyStorageResponse> {
+        Ok(self
+            .inner
+            .verify_storage(self.deps.as_ref(), verify_storage_request)?)
+    }
+
+    fn verifying_keys(&self, circuit: Circuit) -> Result<VerifyingKeysResponse> {
+        Ok(self.inner.verifying_keys(self.deps.as_ref(), circuit)?)
+    }
+
+    fn rebootstrap_history(&self) -> Result<RebootstrapHistoryResponse> {
+        Ok(self.inner.rebootstrap_history(self.deps.as_ref())?)
+    }
+
+    fn subscribers(&self) -> Result<SubscribersResponse> {
+        Ok(self.inner.subscribers(self.deps.as_ref())?)
     }
 }
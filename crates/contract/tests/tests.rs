@@ -1,9 +1,45 @@
-use cw_eth2_lc::eth_utility::compute_sync_committee_period;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coin, coins, from_slice, to_binary, Attribute, BankMsg, Event, Reply, SubMsg, SubMsgResult,
+    Timestamp, Uint256, WasmMsg,
+};
+use cw_eth2_lc::contract::instantiate::CONTRACT_NAME;
+use cw_eth2_lc::contract::optimistic::MAX_FINALITY_DELAY;
+use cw_eth2_lc::contract::Contract;
+use cw_eth2_lc::entrypoint::{execute, migrate, reply, sudo};
+use cw_eth2_lc::eth_utility::{
+    compute_sync_committee_period, Network, NetworkConfig, SLOTS_PER_EPOCH,
+};
+use cw_eth2_lc::events::LightClientEvent;
 use cw_eth2_lc::msg::{
-    ExecutionStateRootResponse, HeadResponse, HeaderRootResponse, SyncCommitteePoseidonHashResponse,
+    AccountState, AdminResponse, Checkpoint, Circuit, ExecuteMsg, ExecutionHeaderInfo,
+    ExecutionStateRootEntry, ExecutionStateRootResponse, FinalizedHeaderInfoResponse, HeadResponse,
+    HeaderRootEntry, HeaderRootResponse, HeaderRootsResponse, LightClientUpdate, MigrateMsg,
+    NearestFinalizedSlotResponse, OldestRetainedSlotResponse, OptimisticConfig,
+    ParticipationConfig, ParticipationConfigResponse, RangeOrder, RelayersResponse,
+    RewardBalanceResponse, RewardConfigResponse, StatusResponse, StorageProof, StorageValue,
+    SubscriberMsg, SubscribersResponse, SubscriptionLimits, SudoMsg,
+    SyncCommitteePoseidonHashResponse, TransactionInfo, VerificationFailure, VerifyAccountRequest,
+    VerifyAccountResponse, VerifyExecutionHeaderRequest, VerifyExecutionHeaderResponse,
+    VerifyLogEntryRequest, VerifyLogEntryResponse, VerifyStorageRequest, VerifyStorageResponse,
+    VerifyTransactionRequest, VerifyTransactionResponse, VerifyWithdrawalRequest,
+    VerifyWithdrawalResponse, Withdrawal,
 };
-use test_utils::accounts;
-use test_utils::test_context::{get_test_context, TestContext};
+use cw_eth2_lc::state::v0_1_0;
+use cw_eth2_lc::ContractError;
+use rlp::RlpStream;
+use test_utils::contract_interface::ContractInterface;
+use test_utils::proofs::{
+    beacon_header_proof, beacon_state_proof, cancun_execution_header, container_proof,
+    execution_payload_index, execution_payload_proof, fake_branch, merkle_root,
+    same_slot_payload_proof, seed_roots, seed_roots_at, Fork, BLOCK_HASH_FIELD,
+    RECEIPTS_ROOT_FIELD, WITHDRAWALS_ROOT_FIELD,
+};
+use test_utils::test_context::{get_test_context, get_test_context_with_input, TestContext};
+use test_utils::trie::Trie;
+use test_utils::unit_test_client::UnitTestContractImplementation;
+use test_utils::{accounts, assert_err_contains, get_test_data, test_env, v0_1_0_deps, TEST_DENOM};
+use types::{near_keccak256, InvalidLengthError, H256};
 
 pub mod test_utils;
 
@@ -20,13 +56,15 @@ pub fn test_submit_correct_lc_update() {
     assert_eq!(
         contract.head().unwrap(),
         HeadResponse {
-            head: lc_updates[0].finalized_slot
+            head: lc_updates[0].finalized_slot,
+            paused: false,
         }
     );
     assert_eq!(
         contract.header_root(lc_updates[0].finalized_slot).unwrap(),
         HeaderRootResponse {
-            header_root: Some(lc_updates[0].finalized_header_root.clone())
+            header_root: Some(lc_updates[0].finalized_header_root.clone()),
+            pending_header_root: None,
         }
     );
     assert_eq!(
@@ -53,13 +91,15 @@ pub fn test_submit_correct_lc_update_identical() {
     assert_eq!(
         contract.head().unwrap(),
         HeadResponse {
-            head: lc_updates[0].finalized_slot
+            head: lc_updates[0].finalized_slot,
+            paused: false,
         }
     );
     assert_eq!(
         contract.header_root(lc_updates[0].finalized_slot).unwrap(),
         HeaderRootResponse {
-            header_root: Some(lc_updates[0].finalized_header_root.clone())
+            header_root: Some(lc_updates[0].finalized_header_root.clone()),
+            pending_header_root: None,
         }
     );
     assert_eq!(
@@ -73,7 +113,6 @@ pub fn test_submit_correct_lc_update_identical() {
 }
 
 #[test]
-#[should_panic(expected = "Failed to verify lc_update proof")]
 pub fn test_submit_incorrect_lc_update_zk_proof() {
     let TestContext {
         mut contract,
@@ -84,11 +123,13 @@ pub fn test_submit_incorrect_lc_update_zk_proof() {
     let mut light_client_update = lc_updates[0].clone();
     light_client_update.lc_update_proof = lc_updates[1].lc_update_proof.clone();
 
-    contract.update_light_client(light_client_update).unwrap();
+    assert_err_contains(
+        contract.update_light_client(light_client_update),
+        "Failed to verify lc_update proof",
+    );
 }
 
 #[test]
-#[should_panic(expected = "Finalized slot is behind head slot")]
 pub fn test_submit_incorrect_lc_update_lower_height() {
     let TestContext {
         mut contract,
@@ -96,11 +137,13 @@ pub fn test_submit_incorrect_lc_update_lower_height() {
         sc_updates: _,
     } = get_test_context(accounts(0));
 
-    contract.update_light_client(lc_updates[1].clone()).unwrap();
+    assert_err_contains(
+        contract.update_light_client(lc_updates[1].clone()),
+        "Finalized slot is behind head slot",
+    );
 }
 
 #[test]
-#[should_panic(expected = "Sync committee hash not known")]
 pub fn test_submit_incorrect_lc_update_unknown_sync_committee() {
     let TestContext {
         mut contract,
@@ -108,7 +151,27 @@ pub fn test_submit_incorrect_lc_update_unknown_sync_committee() {
         sc_updates: _,
     } = get_test_context(accounts(0));
 
-    contract.update_light_client(lc_updates[2].clone()).unwrap();
+    assert_err_contains(
+        contract.update_light_client(lc_updates[2].clone()),
+        "Sync committee hash not known",
+    );
+}
+
+#[test]
+pub fn test_submit_incorrect_lc_update_low_participation() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let mut light_client_update = lc_updates[0].clone();
+    light_client_update.participation = 300;
+
+    assert_err_contains(
+        contract.update_light_client(light_client_update),
+        "Participation is less than 2/3 threshold",
+    );
 }
 
 #[test]
@@ -124,13 +187,15 @@ pub fn test_submit_correct_sc_update() {
     assert_eq!(
         contract.head().unwrap(),
         HeadResponse {
-            head: sc_updates[0].finalized_slot
+            head: sc_updates[0].finalized_slot,
+            paused: false,
         }
     );
     assert_eq!(
         contract.header_root(sc_updates[0].finalized_slot).unwrap(),
         HeaderRootResponse {
-            header_root: Some(sc_updates[0].finalized_header_root.clone())
+            header_root: Some(sc_updates[0].finalized_header_root.clone()),
+            pending_header_root: None,
         }
     );
     assert_eq!(
@@ -174,13 +239,15 @@ pub fn test_submit_correct_sc_update_identical() {
     assert_eq!(
         contract.head().unwrap(),
         HeadResponse {
-            head: sc_updates[0].finalized_slot
+            head: sc_updates[0].finalized_slot,
+            paused: false,
         }
     );
     assert_eq!(
         contract.header_root(sc_updates[0].finalized_slot).unwrap(),
         HeaderRootResponse {
-            header_root: Some(sc_updates[0].finalized_header_root.clone())
+            header_root: Some(sc_updates[0].finalized_header_root.clone()),
+            pending_header_root: None,
         }
     );
     assert_eq!(
@@ -211,7 +278,6 @@ pub fn test_submit_correct_sc_update_identical() {
 }
 
 #[test]
-#[should_panic(expected = "Failed to verify sc_update proof")]
 pub fn test_submit_incorrect_sc_update_zk_proof() {
     let TestContext {
         mut contract,
@@ -225,7 +291,10 @@ pub fn test_submit_incorrect_sc_update_zk_proof() {
         .as_mut()
         .unwrap()
         .sc_update_proof = sc_updates[0].lc_update_proof.clone();
-    contract.update_light_client(light_client_update).unwrap();
+    assert_err_contains(
+        contract.update_light_client(light_client_update),
+        "Failed to verify sc_update proof",
+    );
 }
 
 #[test]
@@ -242,13 +311,15 @@ pub fn test_submit_next_period_lc_update_after_sc_update() {
     assert_eq!(
         contract.head().unwrap(),
         HeadResponse {
-            head: lc_updates[2].finalized_slot
+            head: lc_updates[2].finalized_slot,
+            paused: false,
         }
     );
     assert_eq!(
         contract.header_root(lc_updates[2].finalized_slot).unwrap(),
         HeaderRootResponse {
-            header_root: Some(lc_updates[2].finalized_header_root.clone())
+            header_root: Some(lc_updates[2].finalized_header_root.clone()),
+            pending_header_root: None,
         }
     );
     assert_eq!(
@@ -260,3 +331,3158 @@ pub fn test_submit_next_period_lc_update_after_sc_update() {
         }
     );
 }
+
+#[test]
+pub fn test_admin_transfer() {
+    let TestContext { mut contract, .. } = get_test_context(accounts(0));
+
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::ProposeAdmin {
+                new_admin: accounts(1).to_string(),
+            },
+        ),
+        "Unauthorized",
+    );
+
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::ProposeAdmin {
+                new_admin: accounts(1).to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract.admin().unwrap(),
+        AdminResponse {
+            admin: Some(accounts(0)),
+            pending_admin: Some(accounts(1)),
+        }
+    );
+
+    assert_err_contains(
+        contract.execute(accounts(2), ExecuteMsg::AcceptAdmin {}),
+        "Unauthorized",
+    );
+    contract
+        .execute(accounts(1), ExecuteMsg::AcceptAdmin {})
+        .unwrap();
+    assert_eq!(
+        contract.admin().unwrap(),
+        AdminResponse {
+            admin: Some(accounts(1)),
+            pending_admin: None,
+        }
+    );
+
+    assert_err_contains(
+        contract.execute(accounts(0), ExecuteMsg::RenounceAdmin {}),
+        "Unauthorized",
+    );
+    contract
+        .execute(accounts(1), ExecuteMsg::RenounceAdmin {})
+        .unwrap();
+    assert_eq!(
+        contract.admin().unwrap(),
+        AdminResponse {
+            admin: None,
+            pending_admin: None,
+        }
+    );
+}
+
+#[test]
+pub fn test_register_verifying_key() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let register_msg = |activation_slot| ExecuteMsg::RegisterVerifyingKey {
+        circuit: Circuit::LcUpdate,
+        vkey_string: init_input.vkey_sc_update_string.clone(),
+        activation_slot,
+    };
+
+    assert_err_contains(
+        contract.execute(accounts(1), register_msg(lc_updates[0].attested_slot)),
+        "Unauthorized",
+    );
+    assert_err_contains(
+        contract.execute(accounts(0), register_msg(init_input.head_slot)),
+        "Activation slot",
+    );
+
+    contract
+        .execute(accounts(0), register_msg(lc_updates[0].attested_slot))
+        .unwrap();
+
+    let versions = contract.verifying_keys(Circuit::LcUpdate).unwrap().versions;
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].version, 1);
+    assert_eq!(versions[0].activation_slot, 0);
+    assert_eq!(versions[1].version, 2);
+    assert_eq!(versions[1].activation_slot, lc_updates[0].attested_slot);
+    assert_ne!(versions[0].vkey_hash, versions[1].vkey_hash);
+    assert_eq!(
+        contract
+            .verifying_keys(Circuit::ScUpdate)
+            .unwrap()
+            .versions
+            .len(),
+        1
+    );
+
+    // lc_updates[0] is now checked against the new key, which did not produce its proof
+    assert_err_contains(contract.update_light_client(lc_updates[0].clone()), "proof");
+}
+
+#[test]
+pub fn test_migrate_from_v0_1_0() {
+    let (init_input, lc_updates, _) = get_test_data();
+    let mut contract = UnitTestContractImplementation {
+        inner: Contract::new(mock_env(), None),
+        deps: v0_1_0_deps(init_input.clone()),
+        contracts: vec![],
+    };
+
+    migrate(contract.deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let version = cw2::get_contract_version(&contract.deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    assert!(v0_1_0::NON_MAPPED_LC
+        .may_load(&contract.deps.storage)
+        .unwrap()
+        .is_none());
+    assert!(v0_1_0::NON_MAPPED_SC
+        .may_load(&contract.deps.storage)
+        .unwrap()
+        .is_none());
+
+    assert_eq!(
+        contract.admin().unwrap(),
+        AdminResponse {
+            admin: Some(init_input.admin.clone()),
+            pending_admin: None,
+        }
+    );
+    assert_eq!(
+        contract.header_root(init_input.head_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(init_input.header_root.clone()),
+            pending_header_root: None,
+        }
+    );
+    for circuit in [Circuit::LcUpdate, Circuit::ScUpdate] {
+        let versions = contract.verifying_keys(circuit).unwrap().versions;
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].version, 1);
+        assert_eq!(versions[0].activation_slot, 0);
+    }
+
+    // migrated keys still verify updates
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(
+        contract.head().unwrap(),
+        HeadResponse {
+            head: lc_updates[0].finalized_slot,
+            paused: false,
+        }
+    );
+}
+
+#[test]
+pub fn test_migrate_rejects_downgrade_and_other_contract() {
+    let (init_input, _, _) = get_test_data();
+    let mut deps = v0_1_0_deps(init_input);
+
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+    assert_err_contains(
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).map_err(Into::into),
+        "older version",
+    );
+
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+    assert_err_contains(
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).map_err(Into::into),
+        "Cannot migrate from contract",
+    );
+}
+
+#[test]
+pub fn test_pause_updates() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    assert_err_contains(
+        contract.execute(accounts(1), ExecuteMsg::Pause {}),
+        "Unauthorized",
+    );
+    contract.execute(accounts(0), ExecuteMsg::Pause {}).unwrap();
+    assert_eq!(
+        contract.head().unwrap(),
+        HeadResponse {
+            head: init_input.head_slot,
+            paused: true,
+        }
+    );
+
+    assert_err_contains(
+        contract.update_light_client(lc_updates[0].clone()),
+        "paused",
+    );
+    assert_eq!(
+        contract.header_root(init_input.head_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(init_input.header_root),
+            pending_header_root: None,
+        }
+    );
+
+    assert_err_contains(
+        contract.execute(accounts(1), ExecuteMsg::Unpause {}),
+        "Unauthorized",
+    );
+    contract
+        .execute(accounts(0), ExecuteMsg::Unpause {})
+        .unwrap();
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(
+        contract.head().unwrap(),
+        HeadResponse {
+            head: lc_updates[0].finalized_slot,
+            paused: false,
+        }
+    );
+}
+
+#[test]
+pub fn test_relayer_allowlist() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    let update_msg = ExecuteMsg::UpdateLightClient {
+        light_client_update: lc_updates[0].clone(),
+    };
+
+    assert_eq!(
+        contract.relayers().unwrap(),
+        RelayersResponse {
+            allowlist_enabled: false,
+            relayers: vec![],
+        }
+    );
+
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::SetRelayerAllowlist { enabled: true },
+        ),
+        "Unauthorized",
+    );
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetRelayerAllowlist { enabled: true },
+        )
+        .unwrap();
+    assert_err_contains(
+        contract.execute(accounts(1), update_msg.clone()),
+        "not allowed",
+    );
+
+    let add_msg = ExecuteMsg::AddRelayer {
+        relayer: accounts(1).to_string(),
+    };
+    assert_err_contains(
+        contract.execute(accounts(1), add_msg.clone()),
+        "Unauthorized",
+    );
+    contract.execute(accounts(0), add_msg).unwrap();
+    assert_eq!(
+        contract.relayers().unwrap(),
+        RelayersResponse {
+            allowlist_enabled: true,
+            relayers: vec![accounts(1)],
+        }
+    );
+    contract.execute(accounts(1), update_msg).unwrap();
+
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::RemoveRelayer {
+                relayer: accounts(1).to_string(),
+            },
+        )
+        .unwrap();
+    assert!(contract.relayers().unwrap().relayers.is_empty());
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::UpdateLightClient {
+                light_client_update: lc_updates[1].clone(),
+            },
+        ),
+        "not allowed",
+    );
+}
+
+#[test]
+pub fn test_relayer_allowlist_from_init() {
+    let (mut init_input, lc_updates, _) = get_test_data();
+    init_input.relayers = Some(vec![accounts(1)]);
+    let TestContext { mut contract, .. } = get_test_context_with_input(accounts(0), init_input);
+
+    assert_eq!(
+        contract.relayers().unwrap(),
+        RelayersResponse {
+            allowlist_enabled: true,
+            relayers: vec![accounts(1)],
+        }
+    );
+    assert_err_contains(
+        contract.execute(
+            accounts(2),
+            ExecuteMsg::UpdateLightClient {
+                light_client_update: lc_updates[0].clone(),
+            },
+        ),
+        "not allowed",
+    );
+    contract
+        .execute(
+            accounts(1),
+            ExecuteMsg::UpdateLightClient {
+                light_client_update: lc_updates[0].clone(),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+pub fn test_relayer_rewards() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+    let update = |light_client_update: &LightClientUpdate| ExecuteMsg::UpdateLightClient {
+        light_client_update: light_client_update.clone(),
+    };
+
+    assert_err_contains(
+        contract.execute_with_funds(
+            accounts(2),
+            ExecuteMsg::FundRewards {},
+            &coins(1, TEST_DENOM),
+        ),
+        "Reward config not set",
+    );
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::SetRewardConfig {
+                reward: coin(100, TEST_DENOM),
+            },
+        ),
+        "Unauthorized",
+    );
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetRewardConfig {
+                reward: coin(100, TEST_DENOM),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract.reward_config().unwrap(),
+        RewardConfigResponse {
+            reward: Some(coin(100, TEST_DENOM))
+        }
+    );
+
+    assert_err_contains(
+        contract.execute_with_funds(accounts(2), ExecuteMsg::FundRewards {}, &[]),
+        "must be sent in",
+    );
+    contract
+        .execute_with_funds(
+            accounts(2),
+            ExecuteMsg::FundRewards {},
+            &coins(150, TEST_DENOM),
+        )
+        .unwrap();
+    assert_eq!(
+        contract.reward_balance().unwrap(),
+        RewardBalanceResponse {
+            balance: Some(coin(150, TEST_DENOM))
+        }
+    );
+
+    // advancing the head is paid, resubmitting the same update is not
+    contract
+        .execute(accounts(1), update(&lc_updates[0]))
+        .unwrap();
+    contract
+        .execute(accounts(1), update(&lc_updates[0]))
+        .unwrap();
+    assert_eq!(
+        contract.reward_balance().unwrap().balance,
+        Some(coin(50, TEST_DENOM))
+    );
+
+    // the pool can't cover the reward for the new sync committee, the update still goes through
+    contract
+        .execute(accounts(1), update(&sc_updates[0]))
+        .unwrap();
+    assert_eq!(
+        contract.reward_balance().unwrap().balance,
+        Some(coin(50, TEST_DENOM))
+    );
+    assert!(contract
+        .sync_committee_poseidon_hash(
+            compute_sync_committee_period(sc_updates[0].finalized_slot) + 1
+        )
+        .unwrap()
+        .sync_committee_poseidon_hash
+        .is_some());
+}
+
+#[test]
+pub fn test_submit_lc_update_batch() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+
+    // lc_updates[2] can only be verified once the sync committee from sc_updates[0] is known
+    contract
+        .execute(
+            accounts(1),
+            ExecuteMsg::UpdateLightClientBatch {
+                updates: vec![sc_updates[0].clone(), lc_updates[2].clone()],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        contract.head().unwrap(),
+        HeadResponse {
+            head: lc_updates[2].finalized_slot,
+            paused: false,
+        }
+    );
+    assert_eq!(
+        contract.header_root(sc_updates[0].finalized_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(sc_updates[0].finalized_header_root.clone()),
+            pending_header_root: None,
+        }
+    );
+    assert_eq!(
+        contract.header_root(lc_updates[2].finalized_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(lc_updates[2].finalized_header_root.clone()),
+            pending_header_root: None,
+        }
+    );
+}
+
+#[test]
+pub fn test_submit_incorrect_lc_update_batch() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::UpdateLightClientBatch { updates: vec![] },
+        ),
+        "no updates",
+    );
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::UpdateLightClientBatch {
+                updates: vec![sc_updates[0].clone(), lc_updates[1].clone()],
+            },
+        ),
+        "Update 1 of batch failed: Finalized slot is behind head slot",
+    );
+
+    // the chain reverts the whole transaction, the mock storage of unit tests is not rolled back
+    #[cfg(feature = "e2e")]
+    {
+        let (init_input, _, _) = get_test_data();
+        assert_eq!(
+            contract.head().unwrap(),
+            HeadResponse {
+                head: init_input.head_slot,
+                paused: false,
+            }
+        );
+    }
+}
+
+#[test]
+pub fn test_retention_window() {
+    let (mut init_input, lc_updates, _) = get_test_data();
+    // keep the head only, the init root falls out of the window with the first update
+    init_input.retention_slots = Some(lc_updates[0].finalized_slot - init_input.head_slot - 1);
+    let TestContext { mut contract, .. } =
+        get_test_context_with_input(accounts(0), init_input.clone());
+
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap(),
+        OldestRetainedSlotResponse {
+            slot: Some(init_input.head_slot),
+            retention_slots: init_input.retention_slots,
+        }
+    );
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap().slot,
+        Some(lc_updates[0].finalized_slot)
+    );
+    assert_eq!(
+        contract.header_root(init_input.head_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: None,
+            pending_header_root: None,
+        }
+    );
+    assert_eq!(
+        contract.execution_state_root(init_input.head_slot).unwrap(),
+        ExecutionStateRootResponse {
+            execution_state_root: None
+        }
+    );
+
+    assert_err_contains(
+        contract.execute(accounts(1), ExecuteMsg::SetRetentionWindow { slots: None }),
+        "Unauthorized",
+    );
+    contract
+        .execute(accounts(0), ExecuteMsg::SetRetentionWindow { slots: None })
+        .unwrap();
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap().retention_slots,
+        None
+    );
+}
+
+#[test]
+pub fn test_retention_window_prunes_incrementally() {
+    let (mut init_input, lc_updates, _) = get_test_data();
+    init_input.retention_slots = Some(lc_updates[0].finalized_slot - init_input.head_slot - 1);
+    let mut contract = UnitTestContractImplementation::new(Contract::new(
+        mock_env(),
+        Some(mock_info(accounts(0).as_str(), &[])),
+    ));
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input.clone())
+        .unwrap();
+
+    // backlog of 40 stale roots next to the init root
+    for slot in 1..=40 {
+        contract
+            .inner
+            .state
+            .mapped
+            .header_roots
+            .save(contract.deps.as_mut().storage, slot, &vec![0; 32])
+            .unwrap();
+    }
+
+    let is_stored = |contract: &UnitTestContractImplementation, slot| {
+        contract
+            .inner
+            .state
+            .mapped
+            .header_roots
+            .has(contract.deps.as_ref().storage, slot)
+    };
+    let head_slot = lc_updates[0].finalized_slot;
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert!(!is_stored(&contract, 16));
+    assert!(is_stored(&contract, 17));
+
+    // roots awaiting pruning already read as pruned
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap().slot,
+        Some(head_slot)
+    );
+    assert_eq!(contract.header_root(17).unwrap().header_root, None);
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(Some(17), None)
+            .unwrap()
+            .slot,
+        Some(head_slot)
+    );
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(None, Some(40))
+            .unwrap()
+            .slot,
+        None
+    );
+    assert_eq!(
+        contract.header_roots(Some(17), None, None).unwrap(),
+        HeaderRootsResponse {
+            header_roots: vec![HeaderRootEntry {
+                slot: head_slot,
+                header_root: lc_updates[0].finalized_header_root.clone(),
+            }]
+        }
+    );
+    assert_eq!(
+        contract
+            .header_roots(None, None, Some(RangeOrder::Descending))
+            .unwrap()
+            .header_roots
+            .len(),
+        1
+    );
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert!(is_stored(&contract, 33));
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert!((1..=40).all(|slot| !is_stored(&contract, slot)));
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap().slot,
+        Some(head_slot)
+    );
+}
+
+fn set_optimistic_config(contract: &mut Box<dyn ContractInterface>, challenge_period: u64) {
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetOptimisticConfig {
+                config: Some(OptimisticConfig {
+                    bond: coin(1000, TEST_DENOM),
+                    challenge_period,
+                }),
+            },
+        )
+        .unwrap();
+}
+
+fn submit_optimistic_update(
+    contract: &mut Box<dyn ContractInterface>,
+    light_client_update: &LightClientUpdate,
+) -> cw_eth2_lc::Result<Vec<Event>> {
+    contract.execute_with_funds(
+        accounts(1),
+        ExecuteMsg::SubmitOptimisticUpdate {
+            light_client_update: light_client_update.clone(),
+        },
+        &coins(1000, TEST_DENOM),
+    )
+}
+
+#[test]
+pub fn test_optimistic_update_superseded_by_proof() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    let slot = lc_updates[0].finalized_slot;
+
+    assert_err_contains(
+        submit_optimistic_update(&mut contract, &lc_updates[0]),
+        "disabled",
+    );
+    set_optimistic_config(&mut contract, 3600);
+    assert_err_contains(
+        contract.execute_with_funds(
+            accounts(1),
+            ExecuteMsg::SubmitOptimisticUpdate {
+                light_client_update: lc_updates[0].clone(),
+            },
+            &coins(999, TEST_DENOM),
+        ),
+        "bonded with exactly",
+    );
+
+    submit_optimistic_update(&mut contract, &lc_updates[0]).unwrap();
+    assert_eq!(
+        contract.header_root(slot).unwrap(),
+        HeaderRootResponse {
+            header_root: None,
+            pending_header_root: Some(lc_updates[0].finalized_header_root.clone()),
+        }
+    );
+    assert_eq!(contract.head().unwrap().head, init_input.head_slot);
+    let pending_update = contract
+        .pending_update(slot)
+        .unwrap()
+        .pending_update
+        .unwrap();
+    assert_eq!(pending_update.relayer, accounts(1));
+    assert_eq!(pending_update.bond, coin(1000, TEST_DENOM));
+
+    assert_err_contains(
+        submit_optimistic_update(&mut contract, &lc_updates[0]),
+        "already posted",
+    );
+    assert_err_contains(
+        contract.execute(
+            accounts(2),
+            ExecuteMsg::FinalizeOptimisticUpdate {
+                finalized_slot: slot,
+            },
+        ),
+        "Challenge period",
+    );
+
+    // the proven update agrees with the pending one, which is settled right away
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(
+        contract.header_root(slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(lc_updates[0].finalized_header_root.clone()),
+            pending_header_root: None,
+        }
+    );
+    assert_eq!(contract.pending_update(slot).unwrap().pending_update, None);
+}
+
+#[test]
+pub fn test_optimistic_update_challenge() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    let slot = lc_updates[0].finalized_slot;
+    let challenge = ExecuteMsg::ChallengeOptimisticUpdate {
+        light_client_update: lc_updates[0].clone(),
+        pending_slot: None,
+    };
+    set_optimistic_config(&mut contract, 3600);
+
+    assert_err_contains(
+        contract.execute(accounts(2), challenge.clone()),
+        "No pending update",
+    );
+
+    let mut fraudulent_update = lc_updates[0].clone();
+    fraudulent_update.finalized_header_root = vec![0; 32];
+    submit_optimistic_update(&mut contract, &fraudulent_update).unwrap();
+
+    // a challenge needs a valid proof
+    let mut unproven_update = lc_updates[0].clone();
+    unproven_update.execution_state_root = vec![0; 32];
+    assert_err_contains(
+        contract.execute(
+            accounts(2),
+            ExecuteMsg::ChallengeOptimisticUpdate {
+                light_client_update: unproven_update,
+                pending_slot: None,
+            },
+        ),
+        "proof",
+    );
+
+    contract.execute(accounts(2), challenge.clone()).unwrap();
+    assert_eq!(contract.pending_update(slot).unwrap().pending_update, None);
+    assert_eq!(
+        contract.header_root(slot).unwrap(),
+        HeaderRootResponse {
+            header_root: None,
+            pending_header_root: None,
+        }
+    );
+    assert_eq!(contract.head().unwrap().head, init_input.head_slot);
+
+    submit_optimistic_update(&mut contract, &lc_updates[0]).unwrap();
+    assert_err_contains(
+        contract.execute(accounts(2), challenge),
+        "does not disprove the pending update",
+    );
+}
+
+#[test]
+pub fn test_optimistic_update_bogus_slot() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    set_optimistic_config(&mut contract, 3600);
+    assert_err_contains(
+        contract.execute(
+            accounts(0),
+            ExecuteMsg::SetOptimisticConfig {
+                config: Some(OptimisticConfig {
+                    bond: coin(1000, TEST_DENOM),
+                    challenge_period: u64::MAX,
+                }),
+            },
+        ),
+        "challenge period must not exceed",
+    );
+
+    // slots no proven update could ever finalize
+    let mut beyond_attested = lc_updates[0].clone();
+    beyond_attested.finalized_slot = u64::MAX - 1;
+    let mut too_far_behind = lc_updates[0].clone();
+    too_far_behind.attested_slot = lc_updates[0].finalized_slot + MAX_FINALITY_DELAY + 1;
+    let mut not_started = lc_updates[0].clone();
+    not_started.attested_slot = lc_updates[2].attested_slot + 1000;
+    not_started.finalized_slot = not_started.attested_slot;
+    for update in [beyond_attested, too_far_behind] {
+        assert_err_contains(
+            submit_optimistic_update(&mut contract, &update),
+            "must be at most",
+        );
+    }
+    assert_err_contains(
+        submit_optimistic_update(&mut contract, &not_started),
+        "has not started yet",
+    );
+
+    // a slot after what the chain finalized by the attested slot of lc_updates[0]
+    let mut bogus_update = lc_updates[0].clone();
+    bogus_update.finalized_slot = lc_updates[0].attested_slot - 3;
+    submit_optimistic_update(&mut contract, &bogus_update).unwrap();
+
+    // the chain may finalize past a lower slot, which isn't disproven
+    let mut lower_update = lc_updates[0].clone();
+    lower_update.finalized_slot = lc_updates[0].finalized_slot - 5;
+    submit_optimistic_update(&mut contract, &lower_update).unwrap();
+    assert_err_contains(
+        contract.execute(
+            accounts(2),
+            ExecuteMsg::ChallengeOptimisticUpdate {
+                light_client_update: lc_updates[0].clone(),
+                pending_slot: Some(lower_update.finalized_slot),
+            },
+        ),
+        "does not disprove the pending update",
+    );
+    contract
+        .execute(
+            accounts(2),
+            ExecuteMsg::ChallengeOptimisticUpdate {
+                light_client_update: lc_updates[0].clone(),
+                pending_slot: Some(bogus_update.finalized_slot),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .pending_update(bogus_update.finalized_slot)
+            .unwrap()
+            .pending_update,
+        None
+    );
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(contract.head().unwrap().head, lc_updates[0].finalized_slot);
+}
+
+#[test]
+pub fn test_optimistic_update_finalize() {
+    let (init_input, lc_updates, _) = get_test_data();
+    let TestContext { mut contract, .. } = get_test_context(accounts(0));
+    set_optimistic_config(&mut contract, 0);
+
+    let mut update = lc_updates[0].clone();
+    update.finalized_header_root = vec![7; 32];
+    submit_optimistic_update(&mut contract, &update).unwrap();
+
+    contract.execute(accounts(0), ExecuteMsg::Pause {}).unwrap();
+    let finalize = ExecuteMsg::FinalizeOptimisticUpdate {
+        finalized_slot: update.finalized_slot,
+    };
+    assert_err_contains(contract.execute(accounts(2), finalize.clone()), "paused");
+    contract
+        .execute(accounts(0), ExecuteMsg::Unpause {})
+        .unwrap();
+
+    contract.execute(accounts(2), finalize.clone()).unwrap();
+    assert_eq!(
+        contract.head().unwrap(),
+        HeadResponse {
+            head: update.finalized_slot,
+            paused: false,
+        }
+    );
+    assert_eq!(
+        contract.header_root(update.finalized_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(vec![7; 32]),
+            pending_header_root: None,
+        }
+    );
+    assert_eq!(
+        contract
+            .header_root(init_input.head_slot)
+            .unwrap()
+            .header_root,
+        Some(init_input.header_root)
+    );
+    let info = contract
+        .finalized_header_info(update.finalized_slot)
+        .unwrap()
+        .info
+        .unwrap();
+    assert_eq!(
+        (info.participation, info.attested_slot, info.submitter),
+        (None, Some(update.attested_slot), Some(accounts(1)))
+    );
+    assert_err_contains(contract.execute(accounts(2), finalize), "No pending update");
+}
+
+#[test]
+pub fn test_optimistic_updates_passed_by_head() {
+    let (init_input, lc_updates, _) = get_test_data();
+    let mut contract = UnitTestContractImplementation::new(Contract::new(
+        test_env(),
+        Some(mock_info(accounts(0).as_str(), &[])),
+    ));
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input)
+        .unwrap();
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetOptimisticConfig {
+                config: Some(OptimisticConfig {
+                    bond: coin(1000, TEST_DENOM),
+                    challenge_period: 0,
+                }),
+            },
+        )
+        .unwrap();
+    let head_slot = lc_updates[0].finalized_slot;
+    let mut submit = |relayer: usize, finalized_slot: u64, header_root: Vec<u8>| {
+        let mut update = lc_updates[0].clone();
+        update.finalized_slot = finalized_slot;
+        update.finalized_header_root = header_root;
+        contract
+            .execute_with_funds(
+                accounts(relayer),
+                ExecuteMsg::SubmitOptimisticUpdate {
+                    light_client_update: update,
+                },
+                &coins(1000, TEST_DENOM),
+            )
+            .unwrap();
+    };
+    submit(1, head_slot - 8, vec![1; 32]);
+    submit(2, head_slot - 4, vec![2; 32]);
+    submit(3, head_slot, vec![3; 32]);
+
+    // finalizing a later slot leaves the earlier one behind the head
+    contract
+        .execute(
+            accounts(2),
+            ExecuteMsg::FinalizeOptimisticUpdate {
+                finalized_slot: head_slot - 4,
+            },
+        )
+        .unwrap();
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::FinalizeOptimisticUpdate {
+                finalized_slot: head_slot - 8,
+            },
+        ),
+        "Finalized slot is behind head slot",
+    );
+
+    // the proven update refunds the passed update and slashes the conflicting one
+    let res = execute(
+        contract.deps.as_mut(),
+        test_env(),
+        mock_info(accounts(4).as_str(), &[]),
+        ExecuteMsg::UpdateLightClient {
+            light_client_update: lc_updates[0].clone(),
+        },
+    )
+    .unwrap();
+    let send = |to: usize| {
+        SubMsg::new(BankMsg::Send {
+            to_address: accounts(to).to_string(),
+            amount: coins(1000, TEST_DENOM),
+        })
+    };
+    assert_eq!(res.messages, vec![send(1), send(4)]);
+    for slot in [head_slot - 8, head_slot] {
+        assert_eq!(contract.pending_update(slot).unwrap().pending_update, None);
+    }
+    assert_eq!(
+        contract.header_root(head_slot - 8).unwrap().header_root,
+        None
+    );
+}
+
+#[test]
+pub fn test_rebootstrap() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+
+    // the sync committee for lc_updates[2] is unknown without sc_updates[0]
+    assert_err_contains(
+        contract.update_light_client(lc_updates[2].clone()),
+        "Sync committee hash not known",
+    );
+
+    let checkpoint = Checkpoint {
+        head_slot: lc_updates[2].finalized_slot - 1,
+        header_root: vec![9; 32],
+        execution_state_root: vec![8; 32],
+        sync_committee_poseidon_hash: sc_updates[0]
+            .next_sync_committee
+            .clone()
+            .unwrap()
+            .sync_committee_poseidon_hash,
+    };
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::Rebootstrap {
+                checkpoint: checkpoint.clone(),
+            },
+        ),
+        "Unauthorized",
+    );
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::Rebootstrap {
+                checkpoint: checkpoint.clone(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(contract.head().unwrap().head, checkpoint.head_slot);
+    assert_eq!(
+        contract.header_root(checkpoint.head_slot).unwrap(),
+        HeaderRootResponse {
+            header_root: Some(vec![9; 32]),
+            pending_header_root: None,
+        }
+    );
+    assert_eq!(
+        contract.execution_state_root(checkpoint.head_slot).unwrap(),
+        ExecutionStateRootResponse {
+            execution_state_root: Some(vec![8; 32])
+        }
+    );
+    let records = contract.rebootstrap_history().unwrap().records;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].sender, Some(accounts(0)));
+    assert_eq!(records[0].previous_head_slot, init_input.head_slot);
+    assert_eq!(records[0].checkpoint, checkpoint);
+
+    contract.update_light_client(lc_updates[2].clone()).unwrap();
+    assert_eq!(contract.head().unwrap().head, lc_updates[2].finalized_slot);
+}
+
+#[test]
+pub fn test_rebootstrap_drops_later_state() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    set_optimistic_config(&mut contract, 3600);
+    let mut pending_update = lc_updates[0].clone();
+    pending_update.finalized_slot = lc_updates[0].attested_slot - 3;
+    submit_optimistic_update(&mut contract, &pending_update).unwrap();
+
+    // a bad checkpoint stores a conflicting hash for the next period
+    let next_period = compute_sync_committee_period(lc_updates[2].finalized_slot);
+    let bad_checkpoint = Checkpoint {
+        head_slot: lc_updates[2].finalized_slot - 1,
+        header_root: vec![9; 32],
+        execution_state_root: vec![8; 32],
+        sync_committee_poseidon_hash: vec![7; 32],
+    };
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::Rebootstrap {
+                checkpoint: bad_checkpoint.clone(),
+            },
+        )
+        .unwrap();
+    // the real committee cannot sign against the conflicting hash
+    assert_err_contains(
+        contract.update_light_client(lc_updates[2].clone()),
+        "Failed to verify lc_update proof",
+    );
+
+    let checkpoint = Checkpoint {
+        head_slot: init_input.head_slot,
+        header_root: init_input.header_root.clone(),
+        execution_state_root: init_input.execution_state_root.clone(),
+        sync_committee_poseidon_hash: init_input.sync_committee_poseidon_hash.clone(),
+    };
+    contract
+        .execute(accounts(0), ExecuteMsg::Rebootstrap { checkpoint })
+        .unwrap();
+
+    assert_eq!(contract.head().unwrap().head, init_input.head_slot);
+    for slot in [lc_updates[0].finalized_slot, bad_checkpoint.head_slot] {
+        assert_eq!(contract.header_root(slot).unwrap().header_root, None);
+        assert_eq!(
+            contract
+                .execution_state_root(slot)
+                .unwrap()
+                .execution_state_root,
+            None
+        );
+        assert_eq!(contract.finalized_header_info(slot).unwrap().info, None);
+    }
+    assert_eq!(
+        contract
+            .pending_update(pending_update.finalized_slot)
+            .unwrap()
+            .pending_update,
+        None
+    );
+    assert_eq!(
+        contract
+            .sync_committee_poseidon_hash(next_period)
+            .unwrap()
+            .sync_committee_poseidon_hash,
+        None
+    );
+
+    // the honest committee for the next period is accepted again
+    contract.update_light_client(sc_updates[0].clone()).unwrap();
+    contract.update_light_client(lc_updates[2].clone()).unwrap();
+    assert_eq!(contract.head().unwrap().head, lc_updates[2].finalized_slot);
+}
+
+#[test]
+pub fn test_rebootstrap_refunds_pending_bonds() {
+    let (init_input, lc_updates, _) = get_test_data();
+    let mut contract = UnitTestContractImplementation::new(Contract::new(
+        test_env(),
+        Some(mock_info(accounts(0).as_str(), &[])),
+    ));
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input.clone())
+        .unwrap();
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetOptimisticConfig {
+                config: Some(OptimisticConfig {
+                    bond: coin(1000, TEST_DENOM),
+                    challenge_period: 3600,
+                }),
+            },
+        )
+        .unwrap();
+    contract
+        .execute_with_funds(
+            accounts(1),
+            ExecuteMsg::SubmitOptimisticUpdate {
+                light_client_update: lc_updates[0].clone(),
+            },
+            &coins(1000, TEST_DENOM),
+        )
+        .unwrap();
+
+    let res = execute(
+        contract.deps.as_mut(),
+        test_env(),
+        mock_info(accounts(0).as_str(), &[]),
+        ExecuteMsg::Rebootstrap {
+            checkpoint: Checkpoint {
+                head_slot: init_input.head_slot,
+                header_root: init_input.header_root,
+                execution_state_root: init_input.execution_state_root,
+                sync_committee_poseidon_hash: init_input.sync_committee_poseidon_hash,
+            },
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: accounts(1).to_string(),
+            amount: coins(1000, TEST_DENOM),
+        })]
+    );
+    assert_eq!(
+        contract
+            .pending_update(lc_updates[0].finalized_slot)
+            .unwrap()
+            .pending_update,
+        None
+    );
+}
+
+#[test]
+pub fn test_sudo() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    // governance keeps control after the admin is gone
+    contract
+        .execute(accounts(0), ExecuteMsg::RenounceAdmin {})
+        .unwrap();
+
+    contract.sudo(SudoMsg::Pause {}).unwrap();
+    assert!(contract.head().unwrap().paused);
+    assert_err_contains(
+        contract.update_light_client(lc_updates[0].clone()),
+        "paused",
+    );
+    contract.sudo(SudoMsg::Unpause {}).unwrap();
+    assert!(!contract.head().unwrap().paused);
+
+    contract
+        .sudo(SudoMsg::SetRetentionWindow { slots: Some(8192) })
+        .unwrap();
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap().retention_slots,
+        Some(8192)
+    );
+
+    contract
+        .sudo(SudoMsg::RegisterVerifyingKey {
+            circuit: Circuit::ScUpdate,
+            vkey_string: init_input.vkey_sc_update_string.clone(),
+            activation_slot: init_input.head_slot + 1,
+        })
+        .unwrap();
+    assert_eq!(
+        contract
+            .verifying_keys(Circuit::ScUpdate)
+            .unwrap()
+            .versions
+            .len(),
+        2
+    );
+
+    let checkpoint = Checkpoint {
+        head_slot: lc_updates[0].finalized_slot,
+        header_root: lc_updates[0].finalized_header_root.clone(),
+        execution_state_root: lc_updates[0].execution_state_root.clone(),
+        sync_committee_poseidon_hash: init_input.sync_committee_poseidon_hash.clone(),
+    };
+    let events = contract
+        .sudo(SudoMsg::Rebootstrap {
+            checkpoint: checkpoint.clone(),
+        })
+        .unwrap();
+    assert_eq!(contract.head().unwrap().head, checkpoint.head_slot);
+    assert_eq!(
+        LightClientEvent::decode_all(&events).unwrap().last(),
+        Some(&LightClientEvent::Rebootstrapped {
+            sequence: 0,
+            sender: None,
+            previous_head_slot: init_input.head_slot,
+            head_slot: checkpoint.head_slot,
+        })
+    );
+    let records = contract.rebootstrap_history().unwrap().records;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].sender, None);
+    assert_eq!(records[0].checkpoint, checkpoint);
+
+    // admin messages still need the admin
+    assert_err_contains(
+        contract.execute(accounts(0), ExecuteMsg::Pause {}),
+        "Unauthorized",
+    );
+}
+
+#[test]
+pub fn test_sudo_entrypoint() {
+    let (init_input, _, _) = get_test_data();
+    let mut contract = UnitTestContractImplementation::new(Contract::new(
+        mock_env(),
+        Some(mock_info(accounts(0).as_str(), &[])),
+    ));
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input)
+        .unwrap();
+
+    for (msg, sudo_method) in [
+        (SudoMsg::Pause {}, "pause"),
+        (
+            SudoMsg::AddRelayer {
+                relayer: accounts(1).to_string(),
+            },
+            "add_relayer",
+        ),
+        (
+            SudoMsg::SetRetentionWindow { slots: Some(8192) },
+            "set_retention_window",
+        ),
+    ] {
+        let res = sudo(contract.deps.as_mut(), mock_env(), msg).unwrap();
+        assert!(res.attributes.contains(&Attribute::new("method", "sudo")));
+        assert!(res
+            .attributes
+            .contains(&Attribute::new("sudo_method", sudo_method)));
+    }
+    assert!(contract.head().unwrap().paused);
+    assert_eq!(contract.relayers().unwrap().relayers, vec![accounts(1)]);
+    assert_eq!(
+        contract.oldest_retained_slot().unwrap().retention_slots,
+        Some(8192)
+    );
+
+    // admin transfers are execute only, governance cannot take over the admin
+    for msg in [
+        r#"{"propose_admin":{"new_admin":"eugene"}}"#,
+        r#"{"accept_admin":{}}"#,
+        r#"{"renounce_admin":{}}"#,
+    ] {
+        assert!(from_slice::<SudoMsg>(msg.as_bytes()).is_err());
+    }
+    assert_eq!(
+        contract.admin().unwrap(),
+        AdminResponse {
+            admin: Some(accounts(0)),
+            pending_admin: None,
+        }
+    );
+}
+
+#[test]
+pub fn test_events() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+    let update = |light_client_update: &LightClientUpdate| ExecuteMsg::UpdateLightClient {
+        light_client_update: light_client_update.clone(),
+    };
+
+    let events = contract
+        .execute(accounts(1), update(&lc_updates[0]))
+        .unwrap();
+    assert_eq!(
+        LightClientEvent::decode_all(&events).unwrap(),
+        vec![
+            LightClientEvent::RootRecorded {
+                slot: lc_updates[0].finalized_slot,
+                header_root: lc_updates[0].finalized_header_root.clone(),
+                execution_state_root: lc_updates[0].execution_state_root.clone(),
+            },
+            LightClientEvent::HeadUpdated {
+                slot: lc_updates[0].finalized_slot,
+                period: compute_sync_committee_period(lc_updates[0].finalized_slot),
+                header_root: lc_updates[0].finalized_header_root.clone(),
+                execution_state_root: lc_updates[0].execution_state_root.clone(),
+            },
+        ]
+    );
+
+    // nothing changes when an update is resubmitted
+    let events = contract
+        .execute(accounts(1), update(&lc_updates[0]))
+        .unwrap();
+    assert_eq!(LightClientEvent::decode_all(&events).unwrap(), vec![]);
+
+    // same finalized slot as lc_updates[0], only the sync committee is new
+    let events = contract
+        .execute(accounts(1), update(&sc_updates[0]))
+        .unwrap();
+    assert_eq!(
+        LightClientEvent::decode_all(&events).unwrap(),
+        vec![LightClientEvent::SyncCommitteeAdded {
+            period: compute_sync_committee_period(sc_updates[0].finalized_slot) + 1,
+            sync_committee_poseidon_hash: sc_updates[0]
+                .next_sync_committee
+                .clone()
+                .unwrap()
+                .sync_committee_poseidon_hash,
+        }]
+    );
+
+    // a re-bootstrap records the checkpoint and then itself
+    let (init_input, _, _) = get_test_data();
+    let checkpoint = Checkpoint {
+        head_slot: init_input.head_slot,
+        header_root: init_input.header_root.clone(),
+        execution_state_root: init_input.execution_state_root.clone(),
+        sync_committee_poseidon_hash: init_input.sync_committee_poseidon_hash.clone(),
+    };
+    let events = contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::Rebootstrap {
+                checkpoint: checkpoint.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        LightClientEvent::decode_all(&events).unwrap(),
+        vec![
+            LightClientEvent::RootRecorded {
+                slot: checkpoint.head_slot,
+                header_root: checkpoint.header_root.clone(),
+                execution_state_root: checkpoint.execution_state_root.clone(),
+            },
+            LightClientEvent::HeadUpdated {
+                slot: checkpoint.head_slot,
+                period: compute_sync_committee_period(checkpoint.head_slot),
+                header_root: checkpoint.header_root.clone(),
+                execution_state_root: checkpoint.execution_state_root.clone(),
+            },
+            LightClientEvent::SyncCommitteeAdded {
+                period: compute_sync_committee_period(checkpoint.head_slot),
+                sync_committee_poseidon_hash: checkpoint.sync_committee_poseidon_hash.clone(),
+            },
+            LightClientEvent::Rebootstrapped {
+                sequence: 0,
+                sender: Some(accounts(0)),
+                previous_head_slot: lc_updates[0].finalized_slot,
+                head_slot: checkpoint.head_slot,
+            },
+        ]
+    );
+}
+
+#[test]
+pub fn test_decode_events() {
+    let event = LightClientEvent::RootRecorded {
+        slot: 42,
+        header_root: vec![0xab; 32],
+        execution_state_root: vec![0xcd; 32],
+    };
+    let mut chain_event: Event = event.clone().into();
+    assert_eq!(
+        chain_event.attributes[1].value,
+        "abababababababababababababababababababababababababababababababab"
+    );
+
+    chain_event.ty = format!("wasm-{}", chain_event.ty);
+    assert_eq!(
+        LightClientEvent::decode_all(&[Event::new("wasm"), chain_event.clone()]).unwrap(),
+        vec![event]
+    );
+
+    chain_event.attributes.retain(|attr| attr.key != "slot");
+    assert!(LightClientEvent::decode(&chain_event).is_err());
+
+    // the sender is left out for sudo re-bootstraps
+    for sender in [Some(accounts(0)), None] {
+        let event = LightClientEvent::Rebootstrapped {
+            sequence: 3,
+            sender,
+            previous_head_slot: 100,
+            head_slot: 64,
+        };
+        assert_eq!(
+            LightClientEvent::decode(&event.clone().into()).unwrap(),
+            Some(event)
+        );
+    }
+}
+
+#[test]
+pub fn test_subscriptions() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    let subscriber = contract.instantiate_subscriber();
+    let other_subscriber = contract.instantiate_subscriber();
+
+    assert_err_contains(
+        contract.execute(accounts(3), ExecuteMsg::Subscribe {}),
+        "danny is not a contract and cannot subscribe",
+    );
+    contract
+        .execute(subscriber.clone(), ExecuteMsg::Subscribe {})
+        .unwrap();
+    assert_err_contains(
+        contract.execute(subscriber.clone(), ExecuteMsg::Subscribe {}),
+        "already subscribed",
+    );
+
+    let limits = SubscriptionLimits {
+        max_subscribers: 1,
+        callback_gas_limit: 100_000,
+    };
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::SetSubscriptionLimits {
+                limits: limits.clone(),
+            },
+        ),
+        "Unauthorized",
+    );
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetSubscriptionLimits {
+                limits: limits.clone(),
+            },
+        )
+        .unwrap();
+    assert_err_contains(
+        contract.execute(other_subscriber.clone(), ExecuteMsg::Subscribe {}),
+        "limit of 1 reached",
+    );
+    assert_err_contains(
+        contract.execute(other_subscriber.clone(), ExecuteMsg::Unsubscribe {}),
+        "not subscribed",
+    );
+    assert_eq!(
+        contract.subscribers().unwrap(),
+        SubscribersResponse {
+            subscribers: vec![subscriber.clone()],
+            limits,
+        }
+    );
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(contract.head().unwrap().head, lc_updates[0].finalized_slot);
+
+    assert_err_contains(
+        contract.execute(
+            accounts(1),
+            ExecuteMsg::RemoveSubscriber {
+                subscriber: subscriber.to_string(),
+            },
+        ),
+        "Unauthorized",
+    );
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::RemoveSubscriber {
+                subscriber: subscriber.to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(other_subscriber.clone(), ExecuteMsg::Subscribe {})
+        .unwrap();
+    contract
+        .execute(other_subscriber, ExecuteMsg::Unsubscribe {})
+        .unwrap();
+    assert!(contract.subscribers().unwrap().subscribers.is_empty());
+}
+
+#[test]
+pub fn test_subscriber_callbacks() {
+    let (init_input, lc_updates, _) = get_test_data();
+    let mut contract = UnitTestContractImplementation::new(Contract::new(
+        mock_env(),
+        Some(mock_info(accounts(0).as_str(), &[])),
+    ));
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input)
+        .unwrap();
+    let subscriber = contract.instantiate_subscriber();
+    contract
+        .execute(subscriber.clone(), ExecuteMsg::Subscribe {})
+        .unwrap();
+
+    let update = ExecuteMsg::UpdateLightClient {
+        light_client_update: lc_updates[0].clone(),
+    };
+    let res = execute(
+        contract.deps.as_mut(),
+        mock_env(),
+        mock_info(accounts(1).as_str(), &[]),
+        update.clone(),
+    )
+    .unwrap();
+    let callback = WasmMsg::Execute {
+        contract_addr: subscriber.to_string(),
+        msg: to_binary(&SubscriberMsg::LightClientHeadUpdated {
+            slot: lc_updates[0].finalized_slot,
+            header_root: lc_updates[0].finalized_header_root.clone(),
+            execution_state_root: lc_updates[0].execution_state_root.clone(),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_error(callback, 0)
+            .with_gas_limit(SubscriptionLimits::default().callback_gas_limit)]
+    );
+
+    // subscribers are only notified when the head advances
+    let res = execute(
+        contract.deps.as_mut(),
+        mock_env(),
+        mock_info(accounts(1).as_str(), &[]),
+        update,
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let res = reply(
+        contract.deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 0,
+            result: SubMsgResult::Err("out of gas".to_string()),
+        },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&Attribute::new("subscriber", subscriber)));
+    assert!(res
+        .attributes
+        .contains(&Attribute::new("callback_error", "out of gas")));
+}
+
+#[test]
+pub fn test_participation_config() {
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    let set_config = |threshold_numerator, threshold_denominator, sync_committee_size| {
+        ExecuteMsg::SetParticipationConfig {
+            config: ParticipationConfig {
+                threshold_numerator,
+                threshold_denominator,
+                sync_committee_size,
+            },
+        }
+    };
+
+    assert_eq!(
+        contract.participation_config().unwrap(),
+        ParticipationConfigResponse {
+            config: ParticipationConfig::default()
+        }
+    );
+    assert_err_contains(
+        contract.execute(accounts(1), set_config(9, 10, 512)),
+        "Unauthorized",
+    );
+    for (config, reason) in [
+        (set_config(1, 2, 512), "more than 1/2"),
+        (set_config(4, 3, 512), "must not exceed 1"),
+        (set_config(2, 0, 512), "denominator must not be zero"),
+        (set_config(2, 3, 0), "sync committee size"),
+        (set_config(2, 3, 1024), "sync committee size"),
+    ] {
+        assert_err_contains(contract.execute(accounts(0), config), reason);
+    }
+
+    // a minimal preset committee can't have more participants than members
+    contract.execute(accounts(0), set_config(2, 3, 32)).unwrap();
+    assert_err_contains(
+        contract.update_light_client(lc_updates[0].clone()),
+        "exceeds sync committee size 32",
+    );
+
+    // 435 of 512 signed, enough for 5/6 but not for 9/10
+    contract
+        .execute(accounts(0), set_config(9, 10, 512))
+        .unwrap();
+    assert_err_contains(
+        contract.update_light_client(lc_updates[0].clone()),
+        "Participation is less than 9/10 threshold",
+    );
+    contract
+        .execute(accounts(0), set_config(u64::MAX - 1, u64::MAX, 512))
+        .unwrap();
+    assert_err_contains(
+        contract.update_light_client(lc_updates[0].clone()),
+        "Participation is less than",
+    );
+    contract
+        .execute(accounts(0), set_config(5, 6, 512))
+        .unwrap();
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    assert_eq!(contract.head().unwrap().head, lc_updates[0].finalized_slot);
+}
+
+#[test]
+pub fn test_finalized_header_info() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let info = contract
+        .finalized_header_info(init_input.head_slot)
+        .unwrap()
+        .info
+        .unwrap();
+    assert_eq!(
+        (info.participation, info.attested_slot, info.submitter),
+        (None, None, Some(accounts(0)))
+    );
+
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    let info = contract
+        .finalized_header_info(lc_updates[0].finalized_slot)
+        .unwrap()
+        .info
+        .unwrap();
+    assert_eq!(
+        (info.participation, info.attested_slot, info.submitter),
+        (
+            Some(lc_updates[0].participation),
+            Some(lc_updates[0].attested_slot),
+            Some(accounts(0))
+        )
+    );
+
+    assert_eq!(
+        contract
+            .finalized_header_info(lc_updates[0].finalized_slot + 1)
+            .unwrap(),
+        FinalizedHeaderInfoResponse { info: None }
+    );
+}
+
+#[test]
+pub fn test_range_queries() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates,
+    } = get_test_context(accounts(0));
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    contract.update_light_client(sc_updates[0].clone()).unwrap();
+    contract.update_light_client(lc_updates[2].clone()).unwrap();
+    let slots = |roots: HeaderRootsResponse| -> Vec<u64> {
+        roots.header_roots.iter().map(|entry| entry.slot).collect()
+    };
+
+    let all = vec![
+        init_input.head_slot,
+        lc_updates[0].finalized_slot,
+        lc_updates[2].finalized_slot,
+    ];
+    assert_eq!(slots(contract.header_roots(None, None, None).unwrap()), all);
+    assert_eq!(
+        contract.header_roots(None, Some(1), None).unwrap(),
+        HeaderRootsResponse {
+            header_roots: vec![HeaderRootEntry {
+                slot: init_input.head_slot,
+                header_root: init_input.header_root.clone(),
+            }]
+        }
+    );
+    assert_eq!(
+        slots(
+            contract
+                .header_roots(Some(init_input.head_slot), Some(1), None)
+                .unwrap()
+        ),
+        vec![lc_updates[0].finalized_slot]
+    );
+    assert_eq!(
+        slots(
+            contract
+                .header_roots(None, Some(1), Some(RangeOrder::Descending))
+                .unwrap()
+        ),
+        vec![lc_updates[2].finalized_slot]
+    );
+    assert_eq!(
+        slots(
+            contract
+                .header_roots(
+                    Some(lc_updates[2].finalized_slot),
+                    None,
+                    Some(RangeOrder::Descending)
+                )
+                .unwrap()
+        ),
+        vec![lc_updates[0].finalized_slot, init_input.head_slot]
+    );
+
+    let execution_state_roots = contract
+        .execution_state_roots(Some(init_input.head_slot), None, None)
+        .unwrap()
+        .execution_state_roots;
+    assert_eq!(
+        execution_state_roots,
+        vec![
+            ExecutionStateRootEntry {
+                slot: lc_updates[0].finalized_slot,
+                execution_state_root: lc_updates[0].execution_state_root.clone(),
+            },
+            ExecutionStateRootEntry {
+                slot: lc_updates[2].finalized_slot,
+                execution_state_root: lc_updates[2].execution_state_root.clone(),
+            },
+        ]
+    );
+
+    let periods: Vec<u64> = contract
+        .sync_committee_poseidon_hashes(None, None, Some(RangeOrder::Descending))
+        .unwrap()
+        .sync_committee_poseidon_hashes
+        .iter()
+        .map(|entry| entry.period)
+        .collect();
+    assert_eq!(
+        periods,
+        vec![
+            compute_sync_committee_period(sc_updates[0].finalized_slot) + 1,
+            compute_sync_committee_period(init_input.head_slot),
+        ]
+    );
+}
+
+#[test]
+pub fn test_nearest_finalized_slot() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+    contract.update_light_client(lc_updates[0].clone()).unwrap();
+    let finalized_slot = lc_updates[0].finalized_slot;
+    let nearest = |slot: Option<u64>| NearestFinalizedSlotResponse { slot };
+
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(Some(init_input.head_slot + 1), None)
+            .unwrap(),
+        nearest(Some(finalized_slot))
+    );
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(Some(finalized_slot), None)
+            .unwrap(),
+        nearest(Some(finalized_slot))
+    );
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(Some(finalized_slot + 1), None)
+            .unwrap(),
+        nearest(None)
+    );
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(None, Some(finalized_slot - 1))
+            .unwrap(),
+        nearest(Some(init_input.head_slot))
+    );
+    assert_eq!(
+        contract
+            .nearest_finalized_slot(None, Some(init_input.head_slot - 1))
+            .unwrap(),
+        nearest(None)
+    );
+
+    assert_err_contains(contract.nearest_finalized_slot(None, None), "Exactly one");
+    assert_err_contains(
+        contract.nearest_finalized_slot(Some(finalized_slot), Some(finalized_slot)),
+        "Exactly one",
+    );
+}
+
+#[test]
+pub fn test_status() {
+    let (init_input, _, sc_updates) = get_test_data();
+    // goerli genesis time plus 12 seconds per slot
+    let head_timestamp = 1616508000 + init_input.head_slot * 12;
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(head_timestamp + 100);
+    let mut contract = UnitTestContractImplementation::new(Contract::new(
+        env,
+        Some(mock_info(accounts(0).as_str(), &[])),
+    ));
+    contract
+        .inner
+        .init(contract.deps.as_mut(), init_input.clone())
+        .unwrap();
+
+    let current_period = compute_sync_committee_period(init_input.head_slot);
+    assert_eq!(
+        contract.status(None).unwrap(),
+        StatusResponse {
+            head_slot: init_input.head_slot,
+            head_timestamp,
+            lag: 100,
+            current_period,
+            next_sync_committee_known: false,
+            is_stale: None,
+        }
+    );
+    assert_eq!(contract.status(Some(100)).unwrap().is_stale, Some(false));
+    assert_eq!(contract.status(Some(99)).unwrap().is_stale, Some(true));
+
+    contract.update_light_client(sc_updates[0].clone()).unwrap();
+    assert!(contract.status(None).unwrap().next_sync_committee_known);
+}
+
+#[test]
+pub fn test_config() {
+    let (init_input, _, _) = get_test_data();
+    let TestContext {
+        mut contract,
+        lc_updates,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let config = contract.config().unwrap();
+    assert_eq!(config.admin, Some(init_input.admin.clone()));
+    assert_eq!(config.network, "goerli");
+    assert_eq!(config.genesis_time, 1616508000);
+    assert_eq!(config.capella_fork_version, vec![0x03, 0x00, 0x10, 0x20]);
+    assert_eq!(config.capella_fork_epoch, 162304);
+    assert_eq!(config.participation, ParticipationConfig::default());
+    for (vkey, circuit) in [
+        (config.lc_update_vkey, Circuit::LcUpdate),
+        (config.sc_update_vkey, Circuit::ScUpdate),
+    ] {
+        assert_eq!(
+            vec![vkey],
+            contract.verifying_keys(circuit).unwrap().versions
+        );
+    }
+
+    // keys registered for later slots are reported once the head reaches them
+    let activation_slot = lc_updates[0].attested_slot;
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::RegisterVerifyingKey {
+                circuit: Circuit::LcUpdate,
+                vkey_string: init_input.vkey_sc_update_string.clone(),
+                activation_slot,
+            },
+        )
+        .unwrap();
+    assert_eq!(contract.config().unwrap().lc_update_vkey.version, 1);
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::Rebootstrap {
+                checkpoint: Checkpoint {
+                    head_slot: activation_slot,
+                    header_root: vec![9; 32],
+                    execution_state_root: vec![8; 32],
+                    sync_committee_poseidon_hash: init_input.sync_committee_poseidon_hash,
+                },
+            },
+        )
+        .unwrap();
+    let config = contract.config().unwrap();
+    assert_eq!(config.lc_update_vkey.version, 2);
+    assert_eq!(config.lc_update_vkey.activation_slot, activation_slot);
+    assert_eq!(config.sc_update_vkey.version, 1);
+}
+
+#[test]
+pub fn test_verify_account_and_storage() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let address = vec![0x11; 20];
+    let storage_key = vec![0x22; 32];
+    let storage = Trie::secure([(
+        storage_key.clone(),
+        rlp::encode(&vec![0x01u8, 0x00]).to_vec(),
+    )]);
+    let (storage_root, storage_proof) = (storage.root(), storage.secure_proof(&storage_key));
+    let mut account = RlpStream::new_list(4);
+    account
+        .append(&7u64)
+        .append(&1_000_000u64)
+        .append(&storage_root)
+        .append(&vec![0x33u8; 32]);
+    let state = Trie::secure([(address.clone(), account.out().to_vec())]);
+    let account_proof = state.secure_proof(&address);
+
+    let slot = seed_roots(&mut contract, vec![9; 32], state.root());
+
+    let expected_account = AccountState {
+        nonce: 7,
+        balance: Uint256::from(1_000_000u64),
+        storage_root,
+        code_hash: vec![0x33; 32],
+    };
+    assert_eq!(
+        contract
+            .verify_account(VerifyAccountRequest {
+                slot,
+                address: address.clone(),
+                account_proof: account_proof.clone(),
+            })
+            .unwrap(),
+        VerifyAccountResponse {
+            account: expected_account.clone(),
+        }
+    );
+    assert_eq!(
+        contract
+            .verify_storage(VerifyStorageRequest {
+                slot,
+                address: address.clone(),
+                account_proof: account_proof.clone(),
+                storage_proofs: vec![StorageProof {
+                    key: storage_key.clone(),
+                    proof: storage_proof.clone(),
+                }],
+            })
+            .unwrap(),
+        VerifyStorageResponse {
+            account: expected_account,
+            values: vec![StorageValue {
+                key: storage_key.clone(),
+                value: Uint256::from(256u64),
+            }],
+        }
+    );
+
+    // the leaf is the only path the state has, so it proves other accounts absent
+    assert_eq!(
+        contract
+            .verify_account(VerifyAccountRequest {
+                slot,
+                address: vec![0x12; 20],
+                account_proof: account_proof.clone(),
+            })
+            .unwrap()
+            .account,
+        empty_account()
+    );
+    assert_err_contains(
+        contract.verify_storage(VerifyStorageRequest {
+            slot,
+            address: address.clone(),
+            account_proof: account_proof.clone(),
+            storage_proofs: vec![StorageProof {
+                key: storage_key,
+                proof: account_proof.clone(),
+            }],
+        }),
+        "Invalid trie proof",
+    );
+    assert_err_contains(
+        contract.verify_account(VerifyAccountRequest {
+            slot: slot + 1,
+            address: address.clone(),
+            account_proof: account_proof.clone(),
+        }),
+        "Execution state root not known",
+    );
+    assert_err_contains(
+        contract.verify_account(VerifyAccountRequest {
+            slot,
+            address: vec![0x11; 32],
+            account_proof,
+        }),
+        "Malformed proof request",
+    );
+}
+
+/// Account state of an address without an account
+fn empty_account() -> AccountState {
+    AccountState {
+        nonce: 0,
+        balance: Uint256::zero(),
+        storage_root: near_keccak256(&rlp::NULL_RLP).to_vec(),
+        code_hash: near_keccak256(&[]).to_vec(),
+    }
+}
+
+#[test]
+pub fn test_verify_account_and_storage_exclusion() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    // two storage keys whose hashes share the first nibble, so the storage
+    // trie is an extension over a branch
+    let hashed_nibble = |i: &u8| near_keccak256(&[*i; 32])[0] / 16;
+    let first_key = vec![0u8; 32];
+    let second_key = vec![
+        (1u8..)
+            .find(|i| hashed_nibble(i) == hashed_nibble(&0))
+            .unwrap();
+        32
+    ];
+    let storage = Trie::secure([
+        (first_key.clone(), rlp::encode(&vec![0x2au8]).to_vec()),
+        (
+            second_key.clone(),
+            rlp::encode(&vec![0x01u8, 0x00]).to_vec(),
+        ),
+    ]);
+    let absent_key = vec![0xffu8; 32];
+    assert!(storage.secure_proof(&first_key).len() >= 3);
+
+    // enough accounts for the state trie to branch at its root
+    let address = vec![0x11u8; 20];
+    let account_rlp = |nonce: u64, storage_root: &[u8]| {
+        let mut account = RlpStream::new_list(4);
+        account
+            .append(&nonce)
+            .append(&1_000_000u64)
+            .append(&storage_root)
+            .append(&vec![0x33u8; 32]);
+        account.out().to_vec()
+    };
+    let empty_root = near_keccak256(&rlp::NULL_RLP).to_vec();
+    let mut accounts_state = (1u8..=32)
+        .map(|i| (vec![i; 20], account_rlp(i.into(), &empty_root)))
+        .collect::<Vec<_>>();
+    accounts_state.push((address.clone(), account_rlp(7, &storage.root())));
+    let state = Trie::secure(accounts_state);
+    let absent_address = vec![0xeeu8; 20];
+    assert!(state.secure_proof(&address).len() >= 2);
+
+    let slot = seed_roots(&mut contract, vec![9; 32], state.root());
+
+    let storage_proof = |key: &Vec<u8>| StorageProof {
+        key: key.clone(),
+        proof: storage.secure_proof(key),
+    };
+    assert_eq!(
+        contract
+            .verify_storage(VerifyStorageRequest {
+                slot,
+                address: address.clone(),
+                account_proof: state.secure_proof(&address),
+                storage_proofs: vec![
+                    storage_proof(&first_key),
+                    storage_proof(&second_key),
+                    storage_proof(&absent_key),
+                ],
+            })
+            .unwrap(),
+        VerifyStorageResponse {
+            account: AccountState {
+                nonce: 7,
+                balance: Uint256::from(1_000_000u64),
+                storage_root: storage.root(),
+                code_hash: vec![0x33; 32],
+            },
+            values: vec![
+                StorageValue {
+                    key: first_key.clone(),
+                    value: Uint256::from(42u64),
+                },
+                StorageValue {
+                    key: second_key,
+                    value: Uint256::from(256u64),
+                },
+                StorageValue {
+                    key: absent_key.clone(),
+                    value: Uint256::zero(),
+                },
+            ],
+        }
+    );
+
+    // an absent account has an empty storage trie, proven without nodes
+    assert_eq!(
+        contract
+            .verify_storage(VerifyStorageRequest {
+                slot,
+                address: absent_address.clone(),
+                account_proof: state.secure_proof(&absent_address),
+                storage_proofs: vec![StorageProof {
+                    key: first_key.clone(),
+                    proof: vec![],
+                }],
+            })
+            .unwrap(),
+        VerifyStorageResponse {
+            account: empty_account(),
+            values: vec![StorageValue {
+                key: first_key.clone(),
+                value: Uint256::zero(),
+            }],
+        }
+    );
+
+    // an existing account or slot cannot be passed off as absent
+    let mut truncated_proof = state.secure_proof(&address);
+    truncated_proof.pop();
+    assert_err_contains(
+        contract.verify_account(VerifyAccountRequest {
+            slot,
+            address: address.clone(),
+            account_proof: truncated_proof,
+        }),
+        "proof is missing nodes",
+    );
+    assert_err_contains(
+        contract.verify_storage(VerifyStorageRequest {
+            slot,
+            address: address.clone(),
+            account_proof: state.secure_proof(&address),
+            storage_proofs: vec![StorageProof {
+                key: first_key,
+                proof: vec![],
+            }],
+        }),
+        "proof is missing nodes",
+    );
+    // nor can nodes be appended to an absence proof
+    let mut extended_proof = state.secure_proof(&absent_address);
+    extended_proof.push(state.secure_proof(&address).pop().unwrap());
+    assert_err_contains(
+        contract.verify_account(VerifyAccountRequest {
+            slot,
+            address: absent_address,
+            account_proof: extended_proof,
+        }),
+        "unexpected nodes",
+    );
+}
+
+#[test]
+pub fn test_verify_transaction() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    // EIP-1559 transfer with calldata
+    let mut payload = RlpStream::new_list(12);
+    payload
+        .append(&5u64)
+        .append(&3u64)
+        .append(&1u64)
+        .append(&2u64)
+        .append(&21000u64)
+        .append(&vec![0x44u8; 20])
+        .append(&1_000_000_000_000_000_000u64)
+        .append(&vec![0xabu8, 0xcd])
+        .begin_list(0);
+    payload
+        .append(&0u8)
+        .append(&vec![1u8; 32])
+        .append(&vec![2u8; 32]);
+    let transaction_data = [vec![0x02], payload.out().to_vec()].concat();
+
+    let transaction_index = 0u64;
+    let transactions = Trie::new([(
+        rlp::encode(&transaction_index).to_vec(),
+        transaction_data.clone(),
+    )]);
+    let proof = transactions.proof(&rlp::encode(&transaction_index));
+    let execution_header = cancun_execution_header(&transactions.root());
+
+    // block hash of the payload in the state of the same slot
+    let block_hash_proof = fake_branch(12);
+    let header_root = merkle_root(
+        near_keccak256(&execution_header).to_vec(),
+        execution_payload_index(BLOCK_HASH_FIELD),
+        &block_hash_proof,
+    );
+    let slot = seed_roots(&mut contract, header_root, vec![8; 32]);
+
+    let request = VerifyTransactionRequest {
+        tx_slot: slot,
+        src_slot: None,
+        execution_header: execution_header.clone(),
+        block_hash_proof: block_hash_proof.clone(),
+        transaction_index,
+        transaction_data,
+        proof,
+    };
+    assert_eq!(
+        contract.verify_transaction(request.clone()).unwrap(),
+        VerifyTransactionResponse {
+            src_slot: slot,
+            block_number: 17_000_000,
+            transaction: TransactionInfo {
+                tx_type: 2,
+                chain_id: Some(5),
+                nonce: 3,
+                gas_limit: 21000,
+                to: Some(vec![0x44; 20]),
+                value: Uint256::from(1_000_000_000_000_000_000u64),
+                input: vec![0xab, 0xcd],
+            },
+        }
+    );
+
+    let mut wrong_index = request.clone();
+    wrong_index.transaction_index = 1;
+    assert_err_contains(
+        contract.verify_transaction(wrong_index),
+        "Invalid trie proof",
+    );
+    let mut wrong_branch = request.clone();
+    wrong_branch.block_hash_proof[0] = vec![0xff; 32];
+    let mut short_branch = request.clone();
+    short_branch.block_hash_proof.pop();
+    for request in [wrong_branch, short_branch] {
+        assert_err_contains(
+            contract.verify_transaction(request),
+            "Beacon branch does not match header root",
+        );
+    }
+    let mut wrong_header = request.clone();
+    wrong_header.execution_header[1] ^= 1;
+    assert_err_contains(
+        contract.verify_transaction(wrong_header),
+        "Beacon branch does not match header root",
+    );
+    let mut unknown_slot = request.clone();
+    unknown_slot.tx_slot = slot + 1;
+    assert_err_contains(
+        contract.verify_transaction(unknown_slot),
+        "No finalized header root at or after slot",
+    );
+    let mut unsupported_type = request.clone();
+    unsupported_type.transaction_data[0] = 0x05;
+    assert_err_contains(
+        contract.verify_transaction(unsupported_type),
+        "Unsupported transaction type",
+    );
+
+    // a block after Deneb, whose payload header has 17 fields
+    let deneb_slot = NetworkConfig::new(&Network::Goerli).deneb_fork_epoch * SLOTS_PER_EPOCH + 7;
+    let (header_root, block_hash_proof) = same_slot_payload_proof(
+        Fork::Deneb,
+        BLOCK_HASH_FIELD,
+        near_keccak256(&execution_header).to_vec(),
+    );
+    seed_roots_at(&mut contract, deneb_slot, header_root, vec![8; 32]);
+    let deneb_request = VerifyTransactionRequest {
+        tx_slot: deneb_slot,
+        block_hash_proof,
+        ..request.clone()
+    };
+    assert_eq!(
+        contract.verify_transaction(deneb_request).unwrap().src_slot,
+        deneb_slot
+    );
+    assert_err_contains(
+        contract.verify_transaction(VerifyTransactionRequest {
+            tx_slot: deneb_slot,
+            ..request
+        }),
+        "Beacon branch does not match header root",
+    );
+}
+
+/// Typed transaction envelope with a placeholder signature
+fn typed_transaction(tx_type: u8, fields: RlpStream) -> Vec<u8> {
+    let mut payload = fields;
+    payload
+        .append(&1u8)
+        .append(&vec![1u8; 32])
+        .append(&vec![2u8; 32]);
+    [vec![tx_type], payload.out().to_vec()].concat()
+}
+
+#[test]
+pub fn test_verify_transaction_types() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    // signed example transaction of EIP-155
+    let legacy = hex::decode(
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080\
+         25a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f\
+         761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+    )
+    .unwrap();
+
+    // EIP-2930 contract creation with an access list
+    let mut access_list_fields = RlpStream::new_list(11);
+    access_list_fields
+        .append(&1u64)
+        .append(&4u64)
+        .append(&30_000_000_000u64)
+        .append(&50_000u64)
+        .append_empty_data()
+        .append(&0u64)
+        .append(&vec![0x60u8, 0x80])
+        .begin_list(1)
+        .begin_list(2)
+        .append(&vec![0x77u8; 20])
+        .append_list::<Vec<u8>, _>(&[vec![0u8; 32]]);
+    let access_list_tx = typed_transaction(1, access_list_fields);
+
+    // EIP-1559 transfer
+    let dynamic_fee = |nonce: u64| {
+        let mut fields = RlpStream::new_list(12);
+        fields
+            .append(&1u64)
+            .append(&nonce)
+            .append(&1_000_000_000u64)
+            .append(&40_000_000_000u64)
+            .append(&21_000u64)
+            .append(&vec![0x44u8; 20])
+            .append(&5u64)
+            .append_empty_data()
+            .begin_list(0);
+        typed_transaction(2, fields)
+    };
+
+    // EIP-4844 blob transaction
+    let mut blob_fields = RlpStream::new_list(14);
+    blob_fields
+        .append(&1u64)
+        .append(&9u64)
+        .append(&1_000_000_000u64)
+        .append(&40_000_000_000u64)
+        .append(&100_000u64)
+        .append(&vec![0x99u8; 20])
+        .append(&0u64)
+        .append(&vec![0xdeu8, 0xad])
+        .begin_list(0)
+        .append(&3u64)
+        .append_list::<Vec<u8>, _>(&[[vec![1u8], vec![0x42; 31]].concat()]);
+    let blob_tx = typed_transaction(3, blob_fields);
+
+    // 130 transactions, so the proof of the last one passes through an
+    // extension node as the keys of 128 and 129 share their first three nibbles
+    let mut transactions: Vec<Vec<u8>> = (0..130).map(dynamic_fee).collect();
+    transactions[0] = legacy;
+    transactions[1] = access_list_tx;
+    transactions[129] = blob_tx;
+    let trie = Trie::new(
+        transactions
+            .iter()
+            .enumerate()
+            .map(|(index, data)| (rlp::encode(&(index as u64)).to_vec(), data.clone())),
+    );
+    let execution_header = cancun_execution_header(&trie.root());
+    let block_hash_proof = fake_branch(12);
+    let header_root = merkle_root(
+        near_keccak256(&execution_header).to_vec(),
+        execution_payload_index(BLOCK_HASH_FIELD),
+        &block_hash_proof,
+    );
+    let slot = seed_roots(&mut contract, header_root, vec![8; 32]);
+
+    let expected = [
+        (
+            0u64,
+            TransactionInfo {
+                tx_type: 0,
+                chain_id: Some(1),
+                nonce: 9,
+                gas_limit: 21_000,
+                to: Some(vec![0x35; 20]),
+                value: Uint256::from(1_000_000_000_000_000_000u64),
+                input: vec![],
+            },
+        ),
+        (
+            1,
+            TransactionInfo {
+                tx_type: 1,
+                chain_id: Some(1),
+                nonce: 4,
+                gas_limit: 50_000,
+                to: None,
+                value: Uint256::zero(),
+                input: vec![0x60, 0x80],
+            },
+        ),
+        (
+            2,
+            TransactionInfo {
+                tx_type: 2,
+                chain_id: Some(1),
+                nonce: 2,
+                gas_limit: 21_000,
+                to: Some(vec![0x44; 20]),
+                value: Uint256::from(5u64),
+                input: vec![],
+            },
+        ),
+        (
+            129,
+            TransactionInfo {
+                tx_type: 3,
+                chain_id: Some(1),
+                nonce: 9,
+                gas_limit: 100_000,
+                to: Some(vec![0x99; 20]),
+                value: Uint256::zero(),
+                input: vec![0xde, 0xad],
+            },
+        ),
+    ];
+    assert_eq!(trie.proof(&rlp::encode(&129u64)).len(), 5);
+    for (transaction_index, transaction) in expected {
+        let request = VerifyTransactionRequest {
+            tx_slot: slot,
+            src_slot: None,
+            execution_header: execution_header.clone(),
+            block_hash_proof: block_hash_proof.clone(),
+            transaction_index,
+            transaction_data: transactions[transaction_index as usize].clone(),
+            proof: trie.proof(&rlp::encode(&transaction_index)),
+        };
+        assert_eq!(
+            contract.verify_transaction(request).unwrap(),
+            VerifyTransactionResponse {
+                src_slot: slot,
+                block_number: 17_000_000,
+                transaction,
+            }
+        );
+    }
+
+    // a proof of one transaction does not prove another index
+    assert_err_contains(
+        contract.verify_transaction(VerifyTransactionRequest {
+            tx_slot: slot,
+            src_slot: None,
+            execution_header,
+            block_hash_proof,
+            transaction_index: 128,
+            transaction_data: transactions[129].clone(),
+            proof: trie.proof(&rlp::encode(&129u64)),
+        }),
+        "Invalid trie proof",
+    );
+}
+
+#[test]
+pub fn test_verify_withdrawal() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let withdrawal = Withdrawal {
+        index: 1_000,
+        validator_index: 42,
+        address: vec![0x55; 20],
+        amount: 32_000_000_000,
+    };
+    let chunk = |bytes: &[u8]| [bytes, &vec![0; 32 - bytes.len()]].concat();
+    let withdrawal_root = merkle_root(
+        chunk(&withdrawal.index.to_le_bytes()),
+        4,
+        &[
+            chunk(&withdrawal.validator_index.to_le_bytes()),
+            merkle_root(
+                chunk(&withdrawal.address),
+                2,
+                &[chunk(&withdrawal.amount.to_le_bytes())],
+            ),
+        ],
+    );
+
+    // withdrawals list data root in the payload of the same slot
+    let position = 3;
+    let withdrawal_index =
+        execution_payload_index(WITHDRAWALS_ROOT_FIELD) * 2 * 16 + u128::from(position);
+    let proof = fake_branch(17);
+    let slot = seed_roots(
+        &mut contract,
+        merkle_root(withdrawal_root.clone(), withdrawal_index, &proof),
+        vec![8; 32],
+    );
+
+    let request = VerifyWithdrawalRequest {
+        tx_slot: slot,
+        src_slot: None,
+        withdrawal,
+        position,
+        proof,
+    };
+    assert_eq!(
+        contract.verify_withdrawal(request.clone()).unwrap(),
+        VerifyWithdrawalResponse {
+            verified: true,
+            src_slot: Some(slot),
+            failure_reason: None,
+        }
+    );
+
+    let mut wrong_amount = request.clone();
+    wrong_amount.withdrawal.amount += 1;
+    let mut wrong_position = request.clone();
+    wrong_position.position = 4;
+    let mut short_proof = request.clone();
+    short_proof.proof.pop();
+    let mut unknown_root = request.clone();
+    unknown_root.src_slot = Some(slot + 5);
+    unknown_root.tx_slot = slot + 5;
+    let mut out_of_range = request.clone();
+    out_of_range.position = 16;
+    let mut bad_address = request.clone();
+    bad_address.withdrawal.address = vec![0x55; 19];
+    let mut after_head = request.clone();
+    after_head.tx_slot = slot + 1;
+    for (request, src_slot, failure_reason) in [
+        (
+            wrong_amount,
+            Some(slot),
+            VerificationFailure::BeaconBranchInvalid,
+        ),
+        (
+            wrong_position,
+            Some(slot),
+            VerificationFailure::BeaconBranchInvalid,
+        ),
+        (short_proof, Some(slot), VerificationFailure::MalformedProof),
+        (
+            unknown_root,
+            Some(slot + 5),
+            VerificationFailure::HeaderRootUnknown,
+        ),
+        (out_of_range, None, VerificationFailure::MalformedProof),
+        (bad_address, None, VerificationFailure::MalformedProof),
+        (after_head, None, VerificationFailure::SlotRangeInvalid),
+    ] {
+        assert_eq!(
+            contract.verify_withdrawal(request).unwrap(),
+            VerifyWithdrawalResponse {
+                verified: false,
+                src_slot,
+                failure_reason: Some(failure_reason),
+            }
+        );
+    }
+
+    // a Deneb payload, with the withdrawals list built as an SSZ list of 16
+    let deneb_slot = NetworkConfig::new(&Network::Goerli).deneb_fork_epoch * SLOTS_PER_EPOCH + 3;
+    let mut withdrawals = vec![vec![0x22; 32]; 5];
+    withdrawals[position as usize] = withdrawal_root;
+    let length = chunk(&5u64.to_le_bytes());
+    let (data_root, data_branch) = container_proof(
+        [withdrawals, vec![vec![0; 32]; 11]].concat(),
+        position as usize,
+    );
+    let (header_root, payload_branch) = same_slot_payload_proof(
+        Fork::Deneb,
+        WITHDRAWALS_ROOT_FIELD,
+        merkle_root(data_root, 2, std::slice::from_ref(&length)),
+    );
+    seed_roots_at(&mut contract, deneb_slot, header_root, vec![8; 32]);
+    assert_eq!(
+        contract
+            .verify_withdrawal(VerifyWithdrawalRequest {
+                tx_slot: deneb_slot,
+                src_slot: None,
+                proof: [data_branch, vec![length], payload_branch].concat(),
+                ..request
+            })
+            .unwrap(),
+        VerifyWithdrawalResponse {
+            verified: true,
+            src_slot: Some(deneb_slot),
+            failure_reason: None,
+        }
+    );
+}
+
+#[test]
+pub fn test_verify_execution_header() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let execution_header = cancun_execution_header(&[0xaa; 32]);
+    let block_hash = near_keccak256(&execution_header).to_vec();
+    let block_hash_proof = fake_branch(12);
+    let header_root = merkle_root(
+        block_hash.clone(),
+        execution_payload_index(BLOCK_HASH_FIELD),
+        &block_hash_proof,
+    );
+    let slot = seed_roots(&mut contract, header_root, vec![4; 32]);
+
+    let request = VerifyExecutionHeaderRequest {
+        tx_slot: slot,
+        src_slot: Some(slot),
+        execution_header,
+        block_hash_proof,
+    };
+    assert_eq!(
+        contract.verify_execution_header(request.clone()).unwrap(),
+        VerifyExecutionHeaderResponse {
+            src_slot: slot,
+            header: ExecutionHeaderInfo {
+                block_hash,
+                parent_hash: vec![1; 32],
+                number: 17_000_000,
+                timestamp: 1_700_000_000,
+                base_fee_per_gas: Some(7),
+                gas_limit: Uint256::from(30_000_000u64),
+                gas_used: Uint256::from(21_000u64),
+                state_root: vec![4; 32],
+                transactions_root: vec![0xaa; 32],
+                receipts_root: vec![5; 32],
+                withdrawals_root: Some(vec![8; 32]),
+                parent_beacon_block_root: Some(vec![9; 32]),
+            },
+        }
+    );
+
+    let mut wrong_header = request.clone();
+    wrong_header.execution_header = cancun_execution_header(&[0xbb; 32]);
+    assert_err_contains(
+        contract.verify_execution_header(wrong_header),
+        "Beacon branch does not match header root",
+    );
+    let mut unknown_root = request;
+    unknown_root.src_slot = Some(slot + 1);
+    unknown_root.tx_slot = slot + 1;
+    assert_err_contains(
+        contract.verify_execution_header(unknown_root),
+        "Header root not known for slot",
+    );
+}
+
+#[test]
+pub fn test_verify_execution_header_forks() {
+    let execution_header = cancun_execution_header(&[0xaa; 32]);
+    let block_hash = near_keccak256(&execution_header).to_vec();
+    let first_slot = |epoch: u64| epoch * SLOTS_PER_EPOCH;
+
+    let goerli = NetworkConfig::new(&Network::Goerli);
+    let mainnet = NetworkConfig::new(&Network::Mainnet);
+    let electra_slot = first_slot(mainnet.electra_fork_epoch.unwrap());
+    for (network, slot, fork) in [
+        (
+            Network::Goerli,
+            first_slot(goerli.deneb_fork_epoch) - 1,
+            Fork::Capella,
+        ),
+        (
+            Network::Goerli,
+            first_slot(goerli.deneb_fork_epoch),
+            Fork::Deneb,
+        ),
+        (Network::Mainnet, electra_slot - 1, Fork::Deneb),
+        (Network::Mainnet, electra_slot, Fork::Electra),
+    ] {
+        let (mut init_input, _, _) = get_test_data();
+        init_input.network = network.to_string();
+        let TestContext { mut contract, .. } = get_test_context_with_input(accounts(0), init_input);
+
+        let (header_root, block_hash_proof) =
+            same_slot_payload_proof(fork, BLOCK_HASH_FIELD, block_hash.clone());
+        seed_roots_at(&mut contract, slot, header_root, vec![4; 32]);
+        let request = VerifyExecutionHeaderRequest {
+            tx_slot: slot,
+            src_slot: Some(slot),
+            execution_header: execution_header.clone(),
+            block_hash_proof,
+        };
+        assert_eq!(
+            contract
+                .verify_execution_header(request.clone())
+                .unwrap()
+                .header
+                .block_hash,
+            block_hash,
+            "{fork:?} block at slot {slot} on {network}"
+        );
+
+        // a branch through the containers of another fork doesn't verify
+        let other_fork = match fork {
+            Fork::Capella => Fork::Deneb,
+            _ => Fork::Capella,
+        };
+        let (header_root, block_hash_proof) =
+            same_slot_payload_proof(other_fork, BLOCK_HASH_FIELD, block_hash.clone());
+        seed_roots_at(&mut contract, slot, header_root, vec![4; 32]);
+        assert_err_contains(
+            contract.verify_execution_header(VerifyExecutionHeaderRequest {
+                block_hash_proof,
+                ..request
+            }),
+            "Beacon branch does not match header root",
+        );
+    }
+
+    // a Deneb block proven through the state roots of a later Electra state
+    let (mut init_input, _, _) = get_test_data();
+    init_input.network = Network::Mainnet.to_string();
+    let TestContext { mut contract, .. } = get_test_context_with_input(accounts(0), init_input);
+    let tx_slot = electra_slot - 2;
+    let src_slot = electra_slot + 10;
+    let (tx_state_root, payload_branch) =
+        execution_payload_proof(Fork::Deneb, BLOCK_HASH_FIELD, block_hash.clone());
+    let position = (tx_slot % 8192) as usize;
+    let mut state_roots = vec![vec![0x11; 32]; 8192];
+    state_roots[position] = tx_state_root;
+    let (state_roots_root, state_roots_branch) = container_proof(state_roots, position);
+    let (src_state_root, src_state_branch) = beacon_state_proof(Fork::Electra, 6, state_roots_root);
+    let (header_root, header_branch) = beacon_header_proof(src_state_root);
+    seed_roots_at(&mut contract, src_slot, header_root, vec![4; 32]);
+    assert_eq!(
+        contract
+            .verify_execution_header(VerifyExecutionHeaderRequest {
+                tx_slot,
+                src_slot: Some(src_slot),
+                execution_header,
+                block_hash_proof: [
+                    payload_branch,
+                    state_roots_branch,
+                    src_state_branch,
+                    header_branch,
+                ]
+                .concat(),
+            })
+            .unwrap()
+            .src_slot,
+        src_slot
+    );
+}
+
+#[test]
+pub fn test_verify_execution_header_mainnet_genesis() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let hash = |value: &str| hex::decode(value).unwrap();
+    let empty_trie_root = hash("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+    let mut genesis = RlpStream::new_list(15);
+    genesis
+        .append(&vec![0u8; 32])
+        .append(&hash(
+            "1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+        ))
+        .append(&vec![0u8; 20])
+        .append(&hash(
+            "d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+        ))
+        .append(&empty_trie_root)
+        .append(&empty_trie_root)
+        .append(&vec![0u8; 256])
+        .append(&17_179_869_184u64)
+        .append(&0u64)
+        .append(&5_000u64)
+        .append(&0u64)
+        .append(&0u64)
+        .append(&hash(
+            "11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+        ))
+        .append(&vec![0u8; 32])
+        .append(&hash("0000000000000042"));
+    let execution_header = genesis.out().to_vec();
+    let block_hash = hash("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+
+    let block_hash_proof = fake_branch(12);
+    let header_root = merkle_root(
+        block_hash.clone(),
+        execution_payload_index(BLOCK_HASH_FIELD),
+        &block_hash_proof,
+    );
+    let slot = seed_roots(&mut contract, header_root, vec![8; 32]);
+
+    assert_eq!(
+        contract
+            .verify_execution_header(VerifyExecutionHeaderRequest {
+                tx_slot: slot,
+                src_slot: Some(slot),
+                execution_header,
+                block_hash_proof,
+            })
+            .unwrap()
+            .header,
+        ExecutionHeaderInfo {
+            block_hash,
+            parent_hash: vec![0; 32],
+            number: 0,
+            timestamp: 0,
+            base_fee_per_gas: None,
+            gas_limit: Uint256::from(5_000u64),
+            gas_used: Uint256::zero(),
+            state_root: hash("d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"),
+            transactions_root: empty_trie_root.clone(),
+            receipts_root: empty_trie_root,
+            withdrawals_root: None,
+            parent_beacon_block_root: None,
+        }
+    );
+}
+
+#[test]
+pub fn test_verify_log_entry_failure_reasons() {
+    let TestContext {
+        mut contract,
+        lc_updates: _,
+        sc_updates: _,
+    } = get_test_context(accounts(0));
+
+    let mut log_entry = RlpStream::new_list(3);
+    log_entry
+        .append(&vec![0x66u8; 20])
+        .append_list::<Vec<u8>, _>(&[vec![0x77u8; 32]])
+        .append(&vec![0x88u8; 4]);
+    let log_entry_data = log_entry.out().to_vec();
+    let mut receipt = RlpStream::new_list(4);
+    receipt
+        .append(&1u8)
+        .append(&21_000u64)
+        .append(&vec![0u8; 256])
+        .begin_list(1)
+        .append_raw(&log_entry_data, 1);
+    let receipt_data = receipt.out().to_vec();
+
+    // the proven receipt sits among typed receipts without logs
+    let mut other_receipt = RlpStream::new_list(4);
+    other_receipt
+        .append(&1u8)
+        .append(&50_000u64)
+        .append(&vec![0u8; 256])
+        .begin_list(0);
+    let other_receipt = [vec![0x02], other_receipt.out().to_vec()].concat();
+    let receipt_index = 17u64;
+    let receipts = Trie::new((0..20u64).map(|index| {
+        let data = if index == receipt_index {
+            receipt_data.clone()
+        } else {
+            other_receipt.clone()
+        };
+        (rlp::encode(&index).to_vec(), data)
+    }));
+    let (receipts_root, proof) = (
+        receipts.root(),
+        receipts.proof(&rlp::encode(&receipt_index)),
+    );
+    let receipts_root_proof = fake_branch(12);
+    let header_root = merkle_root(
+        receipts_root.clone(),
+        execution_payload_index(RECEIPTS_ROOT_FIELD),
+        &receipts_root_proof,
+    );
+    let slot = seed_roots(&mut contract, header_root, vec![8; 32]);
+
+    let request = VerifyLogEntryRequest {
+        log_index: 0,
+        log_entry_data,
+        receipt_index,
+        receipt_data,
+        proof,
+        src_slot: None,
+        tx_slot: slot,
+        receipts_root,
+        receipts_root_proof,
+        skip_bridge_call: false,
+        min_participation: None,
+        min_age: None,
+    };
+    assert_eq!(
+        contract.verify_log_entry(request.clone()).unwrap(),
+        VerifyLogEntryResponse {
+            verified: true,
+            src_slot: Some(slot),
+            failure_reason: None,
+        }
+    );
+
+    let mut wrong_log_index = request.clone();
+    wrong_log_index.log_index = 1;
+    let mut malformed_log = request.clone();
+    malformed_log.log_entry_data = vec![0xff];
+    let mut wrong_receipt_index = request.clone();
+    wrong_receipt_index.receipt_index = 1;
+    let mut short_receipts_root = request.clone();
+    short_receipts_root.receipts_root = vec![0; 31];
+    let mut long_receipts_root = request.clone();
+    long_receipts_root.receipts_root = vec![0; 33];
+    let mut after_head = request.clone();
+    after_head.tx_slot = slot + 1;
+    for (request, failure_reason) in [
+        (wrong_log_index, VerificationFailure::LogMismatch),
+        (malformed_log, VerificationFailure::LogMismatch),
+        (wrong_receipt_index, VerificationFailure::TrieProofInvalid),
+        (short_receipts_root, VerificationFailure::MalformedProof),
+        (long_receipts_root, VerificationFailure::MalformedProof),
+        (after_head, VerificationFailure::SlotRangeInvalid),
+    ] {
+        assert_eq!(
+            contract.verify_log_entry(request).unwrap(),
+            VerifyLogEntryResponse {
+                verified: false,
+                src_slot: None,
+                failure_reason: Some(failure_reason),
+            }
+        );
+    }
+
+    let mut skipped = request.clone();
+    skipped.skip_bridge_call = true;
+    skipped.tx_slot = slot + 1;
+    assert_eq!(
+        contract.verify_log_entry(skipped).unwrap(),
+        VerifyLogEntryResponse {
+            verified: true,
+            src_slot: None,
+            failure_reason: None,
+        }
+    );
+
+    let mut unknown_root = request.clone();
+    unknown_root.src_slot = Some(slot + 5);
+    let mut wrong_branch = request.clone();
+    wrong_branch.receipts_root_proof[0] = vec![0xff; 32];
+    let mut short_branch = request.clone();
+    short_branch.receipts_root_proof.pop();
+    let mut long_branch = request.clone();
+    long_branch.receipts_root_proof.push(vec![0; 32]);
+    let mut short_sibling = request.clone();
+    short_sibling.receipts_root_proof[3] = vec![3; 31];
+    let mut unproven_root = request.clone();
+    unproven_root.min_participation = Some(1);
+    let mut after_src_slot = request.clone();
+    after_src_slot.src_slot = Some(slot - 1);
+    let mut before_capella = request.clone();
+    before_capella.src_slot = Some(slot);
+    before_capella.tx_slot = 0;
+    for (request, src_slot, failure_reason) in [
+        (
+            unknown_root,
+            slot + 5,
+            VerificationFailure::HeaderRootUnknown,
+        ),
+        (wrong_branch, slot, VerificationFailure::BeaconBranchInvalid),
+        (short_branch, slot, VerificationFailure::MalformedProof),
+        (long_branch, slot, VerificationFailure::MalformedProof),
+        (short_sibling, slot, VerificationFailure::MalformedProof),
+        (unproven_root, slot, VerificationFailure::RequirementsNotMet),
+        (
+            after_src_slot,
+            slot - 1,
+            VerificationFailure::SlotRangeInvalid,
+        ),
+        (before_capella, slot, VerificationFailure::SlotRangeInvalid),
+    ] {
+        assert_eq!(
+            contract.verify_log_entry(request).unwrap(),
+            VerifyLogEntryResponse {
+                verified: false,
+                src_slot: Some(src_slot),
+                failure_reason: Some(failure_reason),
+            }
+        );
+    }
+
+    contract
+        .execute(
+            accounts(0),
+            ExecuteMsg::SetRetentionWindow { slots: Some(1) },
+        )
+        .unwrap();
+    let mut pruned = request;
+    pruned.src_slot = Some(slot - 2);
+    pruned.tx_slot = slot - 2;
+    assert_eq!(
+        contract.verify_log_entry(pruned).unwrap(),
+        VerifyLogEntryResponse {
+            verified: false,
+            src_slot: Some(slot - 2),
+            failure_reason: Some(VerificationFailure::SlotPruned),
+        }
+    );
+}
+
+/// The proofs below are built with the test trie, so check it against the
+/// roots go-ethereum's trie tests expect
+#[test]
+pub fn test_trie_reference_roots() {
+    let trie = Trie::new([
+        (b"doe".to_vec(), b"reindeer".to_vec()),
+        (b"dog".to_vec(), b"puppy".to_vec()),
+        (b"dogglesworth".to_vec(), b"cat".to_vec()),
+    ]);
+    assert_eq!(
+        hex::encode(trie.root()),
+        "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+    );
+    assert_eq!(
+        hex::encode(Trie::new([(b"A".to_vec(), vec![b'a'; 50])]).root()),
+        "d23786fb4a010da3ce639d66d5e904a11dbc02746d1ce25029e53290cabf28ab"
+    );
+    assert_eq!(
+        hex::encode(Trie::default().root()),
+        "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+    );
+}
+
+#[test]
+pub fn test_hash_conversion_requires_exact_length() {
+    assert_eq!(H256::try_from(vec![1; 32]).unwrap(), H256::from([1; 32]));
+    for len in [31, 33] {
+        assert_eq!(
+            H256::try_from(vec![1; len]),
+            Err(InvalidLengthError { len, expected: 32 })
+        );
+    }
+    assert_eq!(
+        ContractError::from(InvalidLengthError {
+            len: 31,
+            expected: 32
+        })
+        .to_string(),
+        "Invalid hash: expected 32 bytes, got 31"
+    );
+}
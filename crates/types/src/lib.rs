@@ -19,6 +19,30 @@ pub mod eth2;
 #[macro_use]
 pub mod macros;
 
+/// Returned when converting bytes whose length isn't that of the fixed size hash type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidLengthError {
+    pub len: usize,
+    pub expected: usize,
+}
+
+impl InvalidLengthError {
+    pub fn check(len: usize, expected: usize) -> Result<(), Self> {
+        if len != expected {
+            return Err(Self { len, expected });
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for InvalidLengthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {} bytes, got {}", self.expected, self.len)
+    }
+}
+
+impl std::error::Error for InvalidLengthError {}
+
 arr_ethereum_types_wrapper_impl_borsh_serde_ssz!(H64, 8);
 arr_ethereum_types_wrapper_impl_borsh_serde_ssz!(H128, 16);
 arr_ethereum_types_wrapper_impl_borsh_serde_ssz!(H160, 20);
@@ -136,6 +160,20 @@ pub struct BlockHeader {
     #[cfg_attr(all(feature = "eth2"), serde(deserialize_with = "u64_hex_be_option"))]
     pub base_fee_per_gas: Option<u64>,
     pub withdrawals_root: Option<H256>,
+    #[cfg_attr(all(feature = "eth2"), schemars(schema_with = "crate::string_schema"))]
+    #[cfg_attr(
+        all(feature = "eth2"),
+        serde(default, deserialize_with = "u64_hex_be_option")
+    )]
+    pub blob_gas_used: Option<u64>,
+    #[cfg_attr(all(feature = "eth2"), schemars(schema_with = "crate::string_schema"))]
+    #[cfg_attr(
+        all(feature = "eth2"),
+        serde(default, deserialize_with = "u64_hex_be_option")
+    )]
+    pub excess_blob_gas: Option<u64>,
+    pub parent_beacon_block_root: Option<H256>,
+    pub requests_hash: Option<H256>,
 
     pub hash: Option<H256>,
     pub partial_hash: Option<H256>,
@@ -173,6 +211,18 @@ impl BlockHeader {
         if self.withdrawals_root.is_some() {
             list_size += 1;
         }
+        if self.blob_gas_used.is_some() {
+            list_size += 1;
+        }
+        if self.excess_blob_gas.is_some() {
+            list_size += 1;
+        }
+        if self.parent_beacon_block_root.is_some() {
+            list_size += 1;
+        }
+        if self.requests_hash.is_some() {
+            list_size += 1;
+        }
 
         stream.begin_list(list_size);
 
@@ -202,6 +252,22 @@ impl BlockHeader {
         if let Some(withdrawals_root) = &self.withdrawals_root {
             stream.append(withdrawals_root);
         }
+
+        if let Some(blob_gas_used) = &self.blob_gas_used {
+            stream.append(blob_gas_used);
+        }
+
+        if let Some(excess_blob_gas) = &self.excess_blob_gas {
+            stream.append(excess_blob_gas);
+        }
+
+        if let Some(parent_beacon_block_root) = &self.parent_beacon_block_root {
+            stream.append(parent_beacon_block_root);
+        }
+
+        if let Some(requests_hash) = &self.requests_hash {
+            stream.append(requests_hash);
+        }
     }
 
     pub fn calculate_hash(&self) -> H256 {
@@ -240,6 +306,10 @@ impl RlpDecodable for BlockHeader {
             nonce: serialized.val_at(14)?,
             base_fee_per_gas: serialized.val_at(15).ok(),
             withdrawals_root: serialized.val_at(16).ok(),
+            blob_gas_used: serialized.val_at(17).ok(),
+            excess_blob_gas: serialized.val_at(18).ok(),
+            parent_beacon_block_root: serialized.val_at(19).ok(),
+            requests_hash: serialized.val_at(20).ok(),
             hash: None,
             partial_hash: None,
         };
@@ -345,6 +415,75 @@ impl From<RlpDeriveReceipt> for Receipt {
     }
 }
 
+// Transaction
+
+/// Transaction fields shared by legacy and EIP-2718 typed transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// 0 for legacy transactions
+    pub tx_type: u8,
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Vec<u8>,
+}
+
+impl rlp::Decodable for Transaction {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        let view = rlp.as_raw();
+
+        // https://eips.ethereum.org/EIPS/eip-2718#transactions
+        let (tx_type, payload) = match view.first() {
+            Some(&byte) if byte <= 0x7f => (byte, Rlp::new(&view[1..])),
+            _ => (0, rlp.clone()),
+        };
+
+        // chain id and position of the gas limit, the remaining fields follow it
+        let (chain_id, gas_limit_index) = match tx_type {
+            // https://eips.ethereum.org/EIPS/eip-155
+            0 => {
+                let v: u64 = payload.val_at(6)?;
+                ((v >= 35).then(|| (v - 35) / 2), 2)
+            }
+            // https://eips.ethereum.org/EIPS/eip-2930
+            1 => (Some(payload.val_at(0)?), 3),
+            // https://eips.ethereum.org/EIPS/eip-1559
+            // https://eips.ethereum.org/EIPS/eip-4844
+            2 | 3 => (Some(payload.val_at(0)?), 4),
+            _ => return Err(DecoderError::Custom("Unsupported transaction type")),
+        };
+        let nonce_index = if tx_type == 0 { 0 } else { 1 };
+        let to = payload.at(gas_limit_index + 1)?;
+
+        Ok(Transaction {
+            tx_type,
+            chain_id,
+            nonce: payload.val_at(nonce_index)?,
+            gas_limit: payload.val_at(gas_limit_index)?,
+            to: if to.is_empty() {
+                None
+            } else {
+                Some(to.as_val()?)
+            },
+            value: payload.val_at(gas_limit_index + 2)?,
+            input: payload.val_at(gas_limit_index + 3)?,
+        })
+    }
+}
+
+// Account
+
+/// Value of an account in the state trie
+#[derive(Debug, Clone, PartialEq, Eq, RlpDecodableDerive)]
+pub struct Account {
+    pub nonce: u64,
+    pub balance: U256,
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
 pub fn near_sha256(data: &[u8]) -> [u8; 32] {
     let mut buffer = [0u8; 32];
     buffer.copy_from_slice(&sha2::Sha256::digest(data));
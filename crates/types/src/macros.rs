@@ -22,19 +22,22 @@ macro_rules! arr_wrapper_impl_tree_hash_and_borsh {
             }
         }
 
-        impl From<&Vec<u8>> for $name {
-            fn from(item: &Vec<u8>) -> Self {
+        impl TryFrom<&Vec<u8>> for $name {
+            type Error = $crate::InvalidLengthError;
+
+            fn try_from(item: &Vec<u8>) -> Result<Self, Self::Error> {
+                $crate::InvalidLengthError::check(item.len(), $len)?;
                 let mut data = [0u8; $len];
-                for i in 0..item.len() {
-                    data[$len - 1 - i] = item[item.len() - 1 - i];
-                }
-                $name(data.into())
+                data.copy_from_slice(item);
+                Ok($name(data.into()))
             }
         }
 
-        impl From<Vec<u8>> for $name {
-            fn from(item: Vec<u8>) -> Self {
-                (&item).into()
+        impl TryFrom<Vec<u8>> for $name {
+            type Error = $crate::InvalidLengthError;
+
+            fn try_from(item: Vec<u8>) -> Result<Self, Self::Error> {
+                (&item).try_into()
             }
         }
 
@@ -49,7 +52,7 @@ macro_rules! arr_wrapper_impl_tree_hash_and_borsh {
                 }
                 let result =
                     Vec::from_hex(&s).map_err(|err| serde::de::Error::custom(err.to_string()))?;
-                Ok(result.into())
+                result.try_into().map_err(serde::de::Error::custom)
             }
         }
 
@@ -131,25 +134,30 @@ macro_rules! arr_ethereum_types_wrapper_impl_borsh_serde_ssz {
             }
         }
 
-        impl From<&Vec<u8>> for $name {
-            fn from(item: &Vec<u8>) -> Self {
+        impl TryFrom<&[u8]> for $name {
+            type Error = $crate::InvalidLengthError;
+
+            fn try_from(item: &[u8]) -> Result<Self, Self::Error> {
+                $crate::InvalidLengthError::check(item.len(), $len)?;
                 let mut data = [0u8; $len];
-                for i in 0..item.len() {
-                    data[$len - 1 - i] = item[item.len() - 1 - i];
-                }
-                $name(data.into())
+                data.copy_from_slice(item);
+                Ok($name(data.into()))
             }
         }
 
-        impl From<Vec<u8>> for $name {
-            fn from(item: Vec<u8>) -> Self {
-                (&item).into()
+        impl TryFrom<&Vec<u8>> for $name {
+            type Error = $crate::InvalidLengthError;
+
+            fn try_from(item: &Vec<u8>) -> Result<Self, Self::Error> {
+                item.as_slice().try_into()
             }
         }
 
-        impl From<&[u8]> for $name {
-            fn from(item: &[u8]) -> Self {
-                item.to_vec().into()
+        impl TryFrom<Vec<u8>> for $name {
+            type Error = $crate::InvalidLengthError;
+
+            fn try_from(item: Vec<u8>) -> Result<Self, Self::Error> {
+                item.as_slice().try_into()
             }
         }
 
@@ -182,7 +190,7 @@ macro_rules! arr_ethereum_types_wrapper_impl_borsh_serde_ssz {
                 if len != expected {
                     Err(ssz::DecodeError::InvalidByteLength { len, expected })
                 } else {
-                    Ok(bytes.into())
+                    Ok($name(<ethereum_types::$name>::from_slice(bytes)))
                 }
             }
         }